@@ -0,0 +1,284 @@
+use crate::{get_current_os, OperatingSystem};
+use std::path::{Path, PathBuf};
+
+/// Name of the merge driver uyamlt is registered under
+pub(crate) const MERGE_DRIVER_NAME: &str = "unityyamlmerge";
+
+/// Extensions of the Unity YAML assets that should be routed through the merge driver
+pub(crate) const UNITY_YAML_EXTENSIONS: [&str; 3] = ["unity", "prefab", "asset"];
+
+/// How much of uyamlt's git integration is set up in a repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GitIntegrationStatus {
+    /// The merge driver is defined and every Unity YAML extension is mapped to it
+    Configured,
+
+    /// Only some of the integration is in place
+    Partial {
+        /// Whether the repository's git config defines the merge driver
+        driver_configured: bool,
+        /// Extensions that `.gitattributes` doesn't map to the merge driver
+        missing_extensions: Vec<String>,
+    },
+
+    /// Neither the merge driver nor any `.gitattributes` mapping exists
+    Missing,
+}
+
+/// Checks whether a repository's git config and `.gitattributes` are set up for uyamlt.
+/// Only the repository-local config is considered; worktrees and submodules share their
+/// repository's.
+pub fn verify_git_integration(repo_root: &Path) -> GitIntegrationStatus {
+    let driver_configured = find_common_git_dir(repo_root)
+        .and_then(|git_dir| std::fs::read_to_string(git_dir.join("config")).ok())
+        .is_some_and(|config| has_merge_driver(&config));
+    let attributes = std::fs::read_to_string(repo_root.join(".gitattributes")).unwrap_or_default();
+    let missing_extensions: Vec<String> = UNITY_YAML_EXTENSIONS
+        .iter()
+        .filter(|&extension| !maps_extension(&attributes, extension))
+        .map(|&extension| extension.to_owned())
+        .collect();
+
+    if driver_configured && missing_extensions.is_empty() {
+        GitIntegrationStatus::Configured
+    } else if !driver_configured && missing_extensions.len() == UNITY_YAML_EXTENSIONS.len() {
+        GitIntegrationStatus::Missing
+    } else {
+        GitIntegrationStatus::Partial {
+            driver_configured,
+            missing_extensions,
+        }
+    }
+}
+
+/// Produces example git config and `.gitattributes` entries for the running uyamlt binary.
+pub fn example_git_config() -> String {
+    let uyamlt_path = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("uyamlt"));
+    // unsupported platforms can't run unity anyway, so any path style will do
+    let os = get_current_os().unwrap_or(OperatingSystem::Linux);
+    render_example_git_config(os, &uyamlt_path)
+}
+
+pub(crate) fn render_example_git_config(os: OperatingSystem, uyamlt_path: &Path) -> String {
+    // git runs these commands through sh and treats backslashes in config values as escapes,
+    // so Windows paths need forward slashes
+    let mut uyamlt = uyamlt_path.display().to_string();
+    if matches!(os, OperatingSystem::Windows) {
+        uyamlt = uyamlt.replace('\\', "/");
+    }
+    if uyamlt.contains(char::is_whitespace) {
+        uyamlt = format!("\\\"{uyamlt}\\\"");
+    }
+
+    let mut example = format!(
+        r#"# .git/config
+[merge]
+	tool = {MERGE_DRIVER_NAME}
+
+[mergetool "{MERGE_DRIVER_NAME}"]
+	trustExitCode = false
+	keepTemporaries = true
+	keepBackup = false
+	cmd = {uyamlt} merge -p "$BASE" "$REMOTE" "$LOCAL" "$MERGED"
+
+[merge "{MERGE_DRIVER_NAME}"]
+	name = UnityYAMLMerge
+	driver = {uyamlt} --marker-size %L merge --force --fallback none %O %B %A %P
+
+# .gitattributes
+"#
+    );
+    for extension in UNITY_YAML_EXTENSIONS {
+        example.push_str(&format!("*.{extension} merge={MERGE_DRIVER_NAME} eol=lf\n"));
+    }
+
+    example
+}
+
+/// Finds the git directory holding a checkout's config.
+/// Follows the `gitdir:` file worktrees and submodules have instead of a `.git` directory,
+/// and a worktree's `commondir` back to the main repository.
+fn find_common_git_dir(repo_root: &Path) -> Option<PathBuf> {
+    let dot_git = repo_root.join(".git");
+    let git_dir = if dot_git.is_dir() {
+        dot_git
+    } else {
+        let contents = std::fs::read_to_string(&dot_git).ok()?;
+        let git_dir = contents.trim().strip_prefix("gitdir:")?.trim();
+        repo_root.join(git_dir)
+    };
+
+    match std::fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => Some(git_dir.join(common_dir.trim())),
+        Err(_) => Some(git_dir),
+    }
+}
+
+/// Checks whether a git config file defines a `driver` for uyamlt's merge driver
+fn has_merge_driver(config: &str) -> bool {
+    let mut in_driver_section = false;
+    for line in config.lines().map(str::trim) {
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_driver_section = header.split_once(' ').is_some_and(|(section, name)| {
+                section.eq_ignore_ascii_case("merge") && name.trim_matches('"') == MERGE_DRIVER_NAME
+            });
+        } else if in_driver_section {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim().eq_ignore_ascii_case("driver") && !value.trim().is_empty() {
+                    return true;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Checks whether a `.gitattributes` file maps `*.<extension>` to uyamlt's merge driver
+fn maps_extension(attributes: &str, extension: &str) -> bool {
+    let glob = format!("*.{extension}");
+    let merge_attribute = format!("merge={MERGE_DRIVER_NAME}");
+
+    attributes
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .any(|line| {
+            let mut fields = line.split_whitespace();
+            fields
+                .next()
+                .is_some_and(|pattern| pattern == glob || pattern.ends_with(&format!("/{glob}")))
+                && fields.any(|attribute| attribute == merge_attribute)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::fixture_dir;
+
+    const GIT_CONFIG: &str = r#"[core]
+	bare = false
+[merge "unityyamlmerge"]
+	name = UnityYAMLMerge
+	driver = uyamlt --marker-size %L merge --force --fallback none %O %B %A %P
+"#;
+
+    const GITATTRIBUTES: &str = r#"# Unity YAML
+*.unity merge=unityyamlmerge eol=lf
+*.prefab merge=unityyamlmerge eol=lf
+*.asset merge=unityyamlmerge eol=lf
+"#;
+
+    fn repo_fixture(name: &str, config: Option<&str>, attributes: Option<&str>) -> PathBuf {
+        let repo_root = fixture_dir(name);
+        std::fs::create_dir_all(repo_root.join(".git")).unwrap();
+        if let Some(config) = config {
+            std::fs::write(repo_root.join(".git/config"), config).unwrap();
+        }
+        if let Some(attributes) = attributes {
+            std::fs::write(repo_root.join(".gitattributes"), attributes).unwrap();
+        }
+        repo_root
+    }
+
+    #[test]
+    fn test_verify_git_integration_configured() {
+        let repo_root = repo_fixture("git-configured", Some(GIT_CONFIG), Some(GITATTRIBUTES));
+        assert_eq!(
+            verify_git_integration(&repo_root),
+            GitIntegrationStatus::Configured
+        );
+    }
+
+    #[test]
+    fn test_verify_git_integration_attributes_only() {
+        let repo_root = repo_fixture(
+            "git-attributes-only",
+            Some("[core]\n\tbare = false\n"),
+            Some("*.unity merge=unityyamlmerge\n"),
+        );
+        assert_eq!(
+            verify_git_integration(&repo_root),
+            GitIntegrationStatus::Partial {
+                driver_configured: false,
+                missing_extensions: vec!["prefab".to_owned(), "asset".to_owned()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_git_integration_in_worktree() {
+        let repo_root = repo_fixture("git-worktree-main", Some(GIT_CONFIG), None);
+        let worktree_git_dir = repo_root.join(".git/worktrees/feature");
+        std::fs::create_dir_all(&worktree_git_dir).unwrap();
+        std::fs::write(worktree_git_dir.join("commondir"), "../..\n").unwrap();
+        let worktree = fixture_dir("git-worktree-feature");
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", worktree_git_dir.display()),
+        )
+        .unwrap();
+        std::fs::write(worktree.join(".gitattributes"), GITATTRIBUTES).unwrap();
+
+        assert_eq!(
+            verify_git_integration(&worktree),
+            GitIntegrationStatus::Configured
+        );
+    }
+
+    #[test]
+    fn test_verify_git_integration_in_submodule() {
+        let repo_root = repo_fixture("git-submodule-parent", None, None);
+        let module_git_dir = repo_root.join(".git/modules/game");
+        std::fs::create_dir_all(&module_git_dir).unwrap();
+        std::fs::write(module_git_dir.join("config"), GIT_CONFIG).unwrap();
+        let submodule = repo_root.join("game");
+        std::fs::create_dir_all(&submodule).unwrap();
+        std::fs::write(submodule.join(".git"), "gitdir: ../.git/modules/game\n").unwrap();
+        std::fs::write(submodule.join(".gitattributes"), GITATTRIBUTES).unwrap();
+
+        assert_eq!(
+            verify_git_integration(&submodule),
+            GitIntegrationStatus::Configured
+        );
+    }
+
+    #[test]
+    fn test_verify_git_integration_missing() {
+        let repo_root = repo_fixture("git-missing", None, None);
+        assert_eq!(
+            verify_git_integration(&repo_root),
+            GitIntegrationStatus::Missing
+        );
+    }
+
+    #[test]
+    fn test_example_git_config() {
+        let example = render_example_git_config(
+            OperatingSystem::Linux,
+            Path::new("/home/me/.cargo/bin/uyamlt"),
+        );
+        assert!(example.contains(
+            "driver = /home/me/.cargo/bin/uyamlt --marker-size %L merge --force --fallback none %O %B %A %P\n"
+        ));
+        assert!(example.contains("cmd = /home/me/.cargo/bin/uyamlt merge -p "));
+        assert!(example.contains("*.prefab merge=unityyamlmerge eol=lf\n"));
+    }
+
+    #[test]
+    fn test_example_git_config_windows_path() {
+        let example = render_example_git_config(
+            OperatingSystem::Windows,
+            Path::new(r"C:\Program Files\uyamlt\uyamlt.exe"),
+        );
+        assert!(example.contains(
+            r#"driver = \"C:/Program Files/uyamlt/uyamlt.exe\" --marker-size %L merge --force --fallback none %O %B %A %P"#
+        ));
+    }
+}
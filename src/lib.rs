@@ -1,13 +1,22 @@
 use itertools::Itertools;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq, Eq)]
+mod git;
+mod version;
+pub use git::{example_git_config, verify_git_integration, GitIntegrationStatus};
+pub use version::{ReleaseChannel, UnityVersion};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum AppError {
     #[error("Unity is not officially supported in your OS")]
     UnsupportedOS,
 
+    #[error("Unity is not officially supported on your CPU architecture")]
+    UnsupportedArchitecture,
+
     #[error("Could not find unity installation base dir: {0}")]
     BasedirFindIssue(String),
 
@@ -17,6 +26,9 @@ pub enum AppError {
     #[error("Could not access unity installations directory")]
     NoAccessHubInstallations,
 
+    #[error("Could not scan unity installation {0}: {1}")]
+    InstallationScanFailed(String, String),
+
     #[error("Invalid project version file detected")]
     InvalidProjectVersionFile,
 
@@ -26,32 +38,224 @@ pub enum AppError {
     #[error("Could not read ProjectVersion.txt for current project")]
     ProjectVersionFileUnreadable,
 
+    #[error("Invalid unity version: {0}")]
+    InvalidVersion(String),
+
+    #[error("UYAMLT_EDITOR_PATH ({0}) is not a usable editor installation: {1}")]
+    InvalidEditorPath(String, String),
+
     #[error("Could not find UnityYAMLMerge tool")]
     YamlToolNotFound,
+
+    #[error("Unity {0} is required by this project but is not installed{}", .1.as_ref().map_or(String::new(), |closest| format!(" (closest installed: {closest})")))]
+    RequiredVersionNotInstalled(String, Option<String>),
+
+    #[error("Unity {0} has an install directory but no editor binary, the installation is likely incomplete or corrupt (try reinstalling it)")]
+    IncompleteInstallation(String),
+
+    #[error("Could not read install roots file {0}")]
+    RootsFileUnreadable(String),
+
+    #[error("Missing value for argument {0}")]
+    MissingArgumentValue(String),
+
+    #[error("Invalid value {1:?} for argument {0}")]
+    InvalidArgumentValue(String, String),
+}
+
+/// Describes why a specific installation was selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectionSource {
+    /// The installation matches the version pinned in the project's `ProjectVersion.txt`
+    ProjectVersion,
+
+    /// No project was detected, so the latest installation was chosen
+    Latest,
+
+    /// The project's version isn't installed, so the newest final release of the same `year.stream` was chosen
+    PatchFallback,
+
+    /// `UYAMLT_EDITOR_PATH` named the installation to use, regardless of the project's version
+    Forced,
+}
+
+impl std::fmt::Display for SelectionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ProjectVersion => write!(f, "matched project version"),
+            Self::Latest => write!(f, "latest installed"),
+            Self::PatchFallback => write!(f, "same-stream patch fallback"),
+            Self::Forced => write!(f, "forced via UYAMLT_EDITOR_PATH"),
+        }
+    }
+}
+
+/// CI annotation syntax that warnings are additionally rendered in
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Annotations {
+    #[default]
+    None,
+    GitHub,
+}
+
+/// How much progress output is written to stderr; warnings are always shown
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Verbosity {
+    #[default]
+    Quiet,
+    Info,
+    Debug,
+}
+
+/// Where warnings and progress messages go, and how they're rendered.
+/// The default is quiet, with plain warnings, as library callers get it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Reporter {
+    pub annotations: Annotations,
+    pub verbosity: Verbosity,
+}
+
+impl Reporter {
+    /// Writes a progress message to stderr if the verbosity is at least `level`
+    pub(crate) fn log(&self, level: Verbosity, message: &str) {
+        if level <= self.verbosity {
+            let prefix = match level {
+                Verbosity::Quiet => return,
+                Verbosity::Info => "Info",
+                Verbosity::Debug => "Debug",
+            };
+            eprintln!("{prefix}: {message}");
+        }
+    }
+
+    /// Prints a warning to stderr, followed by a CI annotation when enabled via `--annotations`.
+    /// Both go to stderr, so stdout stays machine-readable.
+    pub(crate) fn warn(&self, message: &str) {
+        eprintln!("Warning: {message}");
+        if let Some(annotation) = format_annotation(self.annotations, message) {
+            eprintln!("{annotation}");
+        }
+    }
+}
+
+/// Interprets the value of a boolean environment variable.
+/// Empty, `0`, `false`, `no` and `off` (in any case) are false; anything else is true.
+pub(crate) fn is_truthy(value: &str) -> bool {
+    !matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "" | "0" | "false" | "no" | "off"
+    )
+}
+
+/// Whether the boolean environment variable `name` is set to a truthy value
+pub(crate) fn env_flag(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|value| is_truthy(&value.to_string_lossy()))
+}
+
+/// Interprets `UYAMLT_VERBOSE`: falsy values (see [`is_truthy`]) and `quiet` are quiet,
+/// `2`/`debug` is debug, anything else is info
+pub(crate) fn parse_verbosity(value: &str) -> Verbosity {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "quiet" => Verbosity::Quiet,
+        "2" | "debug" => Verbosity::Debug,
+        value if !is_truthy(value) => Verbosity::Quiet,
+        _ => Verbosity::Info,
+    }
+}
+
+/// Renders a warning as a CI annotation, if annotations are enabled
+pub(crate) fn format_annotation(annotations: Annotations, message: &str) -> Option<String> {
+    match annotations {
+        Annotations::None => None,
+        Annotations::GitHub => Some(format!(
+            "::warning title=uyamlt::{}",
+            message
+                .replace('%', "%25")
+                .replace('\r', "%0D")
+                .replace('\n', "%0A")
+        )),
+    }
 }
 
 // TODO: reconsider hashmap?
-#[derive(Debug, Clone)]
-pub(crate) struct UnityInstallation {
+/// A Unity editor installation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnityInstallation {
     pub version: String,
     pub path: PathBuf,
 }
 type UnityInstallationCollection = Vec<UnityInstallation>;
 
+/// The installations to choose from, and how they were found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Discovery {
+    /// `UYAMLT_EDITOR_PATH` named the one editor to use
+    Forced(UnityInstallation),
+    /// Installations found by scanning install roots
+    Scanned {
+        installations: UnityInstallationCollection,
+        /// Every root that was scanned, in scan order
+        roots: Vec<PathBuf>,
+    },
+}
+
+impl Discovery {
+    pub(crate) fn installations(&self) -> &[UnityInstallation] {
+        match self {
+            Self::Forced(installation) => std::slice::from_ref(installation),
+            Self::Scanned { installations, .. } => installations,
+        }
+    }
+
+    pub(crate) fn into_installations(self) -> UnityInstallationCollection {
+        match self {
+            Self::Forced(installation) => vec![installation],
+            Self::Scanned { installations, .. } => installations,
+        }
+    }
+}
+
 /// Simple enum for marking the operating system.
 /// Note that it only includes the platforms unity supports.
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum OperatingSystem {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatingSystem {
     Windows,
     MacOS,
     Linux,
 }
 
-// TODO: test
-/// Get the current OS based on the version of unity used
+/// Simple enum for marking the CPU architecture.
+/// Note that it only includes the architectures unity supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    X86_64,
+    Arm64,
+}
 
+/// The operating system and architecture uyamlt is running on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Platform {
+    pub os: OperatingSystem,
+    pub arch: Architecture,
+}
+
+/// Get the current OS based on the version of unity used
 pub(crate) fn get_current_os() -> Result<OperatingSystem, AppError> {
-    match std::env::consts::OS {
+    parse_os(std::env::consts::OS)
+}
+
+/// Get the current OS and architecture
+pub fn get_host_platform() -> Result<Platform, AppError> {
+    Ok(Platform {
+        os: get_current_os()?,
+        arch: parse_arch(std::env::consts::ARCH)?,
+    })
+}
+
+/// Maps a `std::env::consts::OS` value to an [`OperatingSystem`]
+pub(crate) fn parse_os(os: &str) -> Result<OperatingSystem, AppError> {
+    match os {
         "windows" => Ok(OperatingSystem::Windows),
         "macos" => Ok(OperatingSystem::MacOS),
         "linux" => Ok(OperatingSystem::Linux),
@@ -59,6 +263,15 @@ pub(crate) fn get_current_os() -> Result<OperatingSystem, AppError> {
     }
 }
 
+/// Maps a `std::env::consts::ARCH` value to an [`Architecture`]
+pub(crate) fn parse_arch(arch: &str) -> Result<Architecture, AppError> {
+    match arch {
+        "x86_64" => Ok(Architecture::X86_64),
+        "aarch64" => Ok(Architecture::Arm64),
+        _ => Err(AppError::UnsupportedArchitecture),
+    }
+}
+
 // TODO: test
 pub(crate) fn get_unityhub_base_app_path(os: OperatingSystem) -> Result<PathBuf, AppError> {
     match os {
@@ -73,10 +286,23 @@ pub(crate) fn get_unityhub_base_app_path(os: OperatingSystem) -> Result<PathBuf,
     }
 }
 
-/// Gets the full path for hub-based unity installations
-pub(crate) fn get_unityhub_base_installations_path(
+/// Gets the full path for hub-based unity installations.
+/// A non-empty `editor_root` (`UYAMLT_EDITOR_ROOT`) replaces the computed path entirely.
+fn get_installations_path_with_override(
     os: OperatingSystem,
+    editor_root: Option<OsString>,
+    reporter: &Reporter,
 ) -> Result<PathBuf, AppError> {
+    if let Some(editor_root) = editor_root.filter(|root| !root.is_empty()) {
+        let path = PathBuf::from(editor_root);
+        if !path.is_dir() {
+            reporter.warn(&format!(
+                "UYAMLT_EDITOR_ROOT ({path:?}) is not an existing directory"
+            ));
+        }
+        return Ok(path);
+    }
+
     // Technically this should've been "more" os-specific,
     // but Unity Hub keeps the same installation scheme across all platforms, so we generalize
     let mut path = get_unityhub_base_app_path(os)?;
@@ -87,182 +313,2679 @@ pub(crate) fn get_unityhub_base_installations_path(
     Ok(path)
 }
 
-// TODO: test
-pub(crate) fn get_unityhub_installations(
+/// Get the path of the Unity binary inside an editor directory.
+/// On macOS, Unity Hub installs the editor as an app bundle next to the other modules.
+pub(crate) fn get_unity_exe_path(os: OperatingSystem, editor_path: &Path) -> PathBuf {
+    editor_path.join(match os {
+        OperatingSystem::Windows => "Editor/Unity.exe",
+        OperatingSystem::MacOS => "Unity.app/Contents/MacOS/Unity",
+        OperatingSystem::Linux => "Editor/Unity",
+    })
+}
+
+/// Discovers the installations to choose from.
+/// Setting `UYAMLT_EDITOR_PATH` to an editor directory skips discovery and forces that editor.
+pub(crate) fn get_installations(
+    os: OperatingSystem,
+    extra_roots: &[PathBuf],
+    parallel_scan: bool,
+    reporter: &Reporter,
+) -> Result<Discovery, AppError> {
+    match std::env::var_os("UYAMLT_EDITOR_PATH").filter(|path| !path.is_empty()) {
+        Some(editor_path) => Ok(Discovery::Forced(get_located_installation(
+            os,
+            Path::new(&editor_path),
+        )?)),
+        None => {
+            let roots = get_unityhub_roots(os, extra_roots, reporter)?;
+            Ok(Discovery::Scanned {
+                installations: scan_unityhub_roots(os, &roots, parallel_scan, reporter)?,
+                roots,
+            })
+        }
+    }
+}
+
+/// Builds an installation from an editor directory outside Unity Hub.
+/// The directory must be named after its version, as Unity Hub names them.
+pub(crate) fn get_located_installation(
+    os: OperatingSystem,
+    editor_path: &Path,
+) -> Result<UnityInstallation, AppError> {
+    let invalid = |reason: &str| {
+        AppError::InvalidEditorPath(editor_path.display().to_string(), reason.to_owned())
+    };
+
+    if !get_unity_exe_path(os, editor_path).is_file() {
+        return Err(invalid("no Unity binary found in it"));
+    }
+    let version = editor_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(normalize_version)
+        .filter(|version| version.parse::<UnityVersion>().is_ok())
+        .ok_or_else(|| invalid("its directory name is not a Unity version"))?;
+
+    Ok(UnityInstallation {
+        version,
+        path: editor_path.to_path_buf(),
+    })
+}
+
+/// Gets the directory Unity Hub keeps its own configuration in
+pub(crate) fn get_unityhub_config_path(os: OperatingSystem) -> Result<PathBuf, AppError> {
+    let env_path = |name: &str| {
+        std::env::var_os(name)
+            .map(PathBuf::from)
+            .ok_or_else(|| AppError::BasedirFindIssue(format!("{name} is not set")))
+    };
+
+    match os {
+        OperatingSystem::Windows => Ok(env_path("APPDATA")?.join("UnityHub")),
+        OperatingSystem::MacOS => {
+            Ok(env_path("HOME")?.join("Library/Application Support/UnityHub"))
+        }
+        OperatingSystem::Linux => Ok(env_path("HOME")?.join(".config/UnityHub")),
+    }
+}
+
+/// Parses Unity Hub's `secondaryInstallPath.json`, which holds a single JSON string.
+/// Returns `None` if the file is malformed or the path is empty (Hub's "not configured").
+pub(crate) fn parse_secondary_install_path(contents: &str) -> Option<PathBuf> {
+    let quoted = contents.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut path = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                escaped @ ('"' | '\\' | '/') => path.push(escaped),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    if code.len() != 4 {
+                        return None;
+                    }
+                    path.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                _ => return None,
+            },
+            '"' => return None,
+            c => path.push(c),
+        }
+    }
+
+    Some(PathBuf::from(path)).filter(|path| !path.as_os_str().to_string_lossy().trim().is_empty())
+}
+
+/// Reads the secondary install path configured in Unity Hub, if there is one.
+/// A missing or malformed config file is treated as no secondary install path.
+pub(crate) fn get_secondary_install_path(config_path: &Path) -> Option<PathBuf> {
+    let contents = std::fs::read_to_string(config_path.join("secondaryInstallPath.json")).ok()?;
+    parse_secondary_install_path(&contents)
+}
+
+/// Lists the roots to scan: Unity Hub's editor directory and its configured secondary
+/// install path, followed by any extra install roots.
+/// Setting `UYAMLT_EDITOR_ROOT` replaces both of Unity Hub's roots.
+pub(crate) fn get_unityhub_roots(
+    os: OperatingSystem,
+    extra_roots: &[PathBuf],
+    reporter: &Reporter,
+) -> Result<Vec<PathBuf>, AppError> {
+    get_roots_with_override(
+        os,
+        std::env::var_os("UYAMLT_EDITOR_ROOT"),
+        get_unityhub_config_path(os).ok(),
+        extra_roots,
+        reporter,
+    )
+}
+
+fn get_roots_with_override(
+    os: OperatingSystem,
+    editor_root: Option<OsString>,
+    config_path: Option<PathBuf>,
+    extra_roots: &[PathBuf],
+    reporter: &Reporter,
+) -> Result<Vec<PathBuf>, AppError> {
+    let overridden = editor_root.as_ref().is_some_and(|root| !root.is_empty());
+    let secondary_install_path = config_path
+        .filter(|_| !overridden)
+        .and_then(|config_path| get_secondary_install_path(&config_path));
+    Ok(unityhub_roots(
+        get_installations_path_with_override(os, editor_root, reporter)?,
+        secondary_install_path,
+        extra_roots,
+    ))
+}
+
+/// Orders the install roots, skipping a secondary path that duplicates the default one
+pub(crate) fn unityhub_roots(
+    base_installation_path: PathBuf,
+    secondary_install_path: Option<PathBuf>,
+    extra_roots: &[PathBuf],
+) -> Vec<PathBuf> {
+    let secondary_install_path =
+        secondary_install_path.filter(|path| *path != base_installation_path);
+    std::iter::once(base_installation_path)
+        .chain(secondary_install_path)
+        .chain(extra_roots.iter().cloned())
+        .collect()
+}
+
+/// Discovers installations in the given install roots, the first of which is Unity Hub's editor directory.
+/// When a version exists in several roots, the first one found wins.
+pub(crate) fn scan_unityhub_roots(
+    os: OperatingSystem,
+    roots: &[PathBuf],
+    parallel_scan: bool,
+    reporter: &Reporter,
+) -> Result<UnityInstallationCollection, AppError> {
+    let progress = ScanProgress::on_stderr(reporter);
+    let installations = match roots {
+        [base_installation_path] => {
+            scan_installations(os, base_installation_path, progress.as_ref(), reporter)
+        }
+        _ => scan_installation_roots(os, roots, parallel_scan, progress.as_ref(), reporter).map(
+            |installations| {
+                installations
+                    .into_iter()
+                    .unique_by(|x| x.version.clone())
+                    .collect()
+            },
+        ),
+    };
+    if let Some(progress) = progress {
+        progress
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .finish();
+    }
+
+    installations
+}
+
+/// Discovers installations in each of the given install roots, skipping (with a warning) roots that can't be scanned.
+/// A real IO failure (rather than a missing root) is an error if it hits the first root, or if no root could be scanned.
+/// With `parallel_scan`, every root is scanned on its own thread; results keep the order of `roots` either way.
+/// All roots count towards the same `progress`.
+pub(crate) fn scan_installation_roots(
+    os: OperatingSystem,
+    roots: &[PathBuf],
+    parallel_scan: bool,
+    progress: Option<&std::sync::Mutex<ScanProgress>>,
+    reporter: &Reporter,
+) -> Result<UnityInstallationCollection, AppError> {
+    let scans: Vec<Result<UnityInstallationCollection, AppError>> = if parallel_scan {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = roots
+                .iter()
+                .map(|root| scope.spawn(move || scan_installations(os, root, progress, reporter)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("install root scan panicked"))
+                .collect()
+        })
+    } else {
+        roots
+            .iter()
+            .map(|root| scan_installations(os, root, progress, reporter))
+            .collect()
+    };
+
+    let is_io_failure = |err: &AppError| matches!(err, AppError::InstallationScanFailed(..));
+    if let Some(Err(err)) = scans
+        .first()
+        .filter(|scan| scan.as_ref().is_err_and(is_io_failure))
+    {
+        return Err(err.clone());
+    }
+    if scans.iter().all(Result::is_err) {
+        if let Some(Err(err)) = scans
+            .iter()
+            .find(|scan| scan.as_ref().is_err_and(is_io_failure))
+        {
+            return Err(err.clone());
+        }
+    }
+
+    Ok(roots
+        .iter()
+        .zip(scans)
+        .filter_map(|(root, scan)| match scan {
+            Ok(installations) => Some(installations),
+            Err(err) => {
+                reporter.warn(&format!("skipping install root {root:?}: {err}"));
+                None
+            }
+        })
+        .flatten()
+        .collect())
+}
+
+/// Reports how many directories a long scan has gone through so far
+pub(crate) struct ScanProgress {
+    out: Box<dyn std::io::Write + Send>,
+    started: std::time::Instant,
+    threshold: std::time::Duration,
+    interval: std::time::Duration,
+    last_report: Option<std::time::Instant>,
+    scanned: usize,
+}
+
+impl ScanProgress {
+    /// Reports to `out` once scanning has taken longer than `threshold`, then every `interval`
+    pub(crate) fn new(
+        out: Box<dyn std::io::Write + Send>,
+        threshold: std::time::Duration,
+        interval: std::time::Duration,
+    ) -> Self {
+        Self {
+            out,
+            started: std::time::Instant::now(),
+            threshold,
+            interval,
+            last_report: None,
+            scanned: 0,
+        }
+    }
+
+    /// Progress on stderr, if it's a terminal.
+    /// `UYAMLT_PROGRESS_AFTER_MS` and `UYAMLT_PROGRESS_INTERVAL_MS` configure its timing.
+    pub(crate) fn on_stderr(reporter: &Reporter) -> Option<std::sync::Mutex<Self>> {
+        use std::io::IsTerminal;
+
+        std::io::stderr().is_terminal().then(|| {
+            std::sync::Mutex::new(Self::new(
+                Box::new(std::io::stderr()),
+                env_millis("UYAMLT_PROGRESS_AFTER_MS", 2000, reporter),
+                env_millis("UYAMLT_PROGRESS_INTERVAL_MS", 500, reporter),
+            ))
+        })
+    }
+
+    /// Counts one more scanned directory, reporting if it's time to
+    pub(crate) fn tick(&mut self) {
+        self.scanned += 1;
+        let now = std::time::Instant::now();
+        let due = match self.last_report {
+            None => now.duration_since(self.started) >= self.threshold,
+            Some(last) => now.duration_since(last) >= self.interval,
+        };
+        if due {
+            self.last_report = Some(now);
+            // Progress is best effort, a broken stderr shouldn't fail the scan
+            let _ = write!(
+                self.out,
+                "\rscanning install roots: {} directories so far",
+                self.scanned
+            );
+            let _ = self.out.flush();
+        }
+    }
+
+    /// Ends the progress line, if one was started
+    pub(crate) fn finish(&mut self) {
+        if self.last_report.is_some() {
+            let _ = writeln!(self.out);
+        }
+    }
+}
+
+/// Reads a duration in milliseconds from the environment variable `name`, warning if it's invalid
+pub(crate) fn env_millis(name: &str, default: u64, reporter: &Reporter) -> std::time::Duration {
+    let millis = match std::env::var(name) {
+        Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+            reporter.warn(&format!(
+                "ignoring {name}={value:?}: expected a number of milliseconds"
+            ));
+            default
+        }),
+        Err(_) => default,
+    };
+    std::time::Duration::from_millis(millis)
+}
+
+/// Discovers the installations directly inside an editors directory, counting every scanned
+/// directory towards `progress`.
+/// Entries whose names aren't valid unicode are skipped with a warning; failing to read an entry is an error.
+pub(crate) fn scan_installations(
     os: OperatingSystem,
+    base_installation_path: &Path,
+    progress: Option<&std::sync::Mutex<ScanProgress>>,
+    reporter: &Reporter,
 ) -> Result<UnityInstallationCollection, AppError> {
     let mut installations = UnityInstallationCollection::new();
+    let mut unreadable_editors = Vec::new();
+    let scan_failed = |path: &Path, err: std::io::Error| {
+        AppError::InstallationScanFailed(path.display().to_string(), err.to_string())
+    };
 
-    let base_installation_path = get_unityhub_base_installations_path(os)?;
-    let readdir = std::fs::read_dir(base_installation_path)
-        .map_err(|_| AppError::NoAccessHubInstallations)?;
-    for entry in readdir.filter_map(Result::ok) {
-        let editor_path: PathBuf = entry.path();
+    let readdir = std::fs::read_dir(base_installation_path).map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => AppError::NoAccessHubInstallations,
+        _ => scan_failed(base_installation_path, err),
+    })?;
+    for entry in readdir {
+        let editor_path: PathBuf = entry
+            .map_err(|err| scan_failed(base_installation_path, err))?
+            .path();
         if !editor_path.is_dir() {
             continue;
         }
+        if let Some(progress) = progress {
+            progress
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .tick();
+        }
 
-        let unity_exe_path = editor_path.join("Editor").join(match os {
-            OperatingSystem::Windows => "Unity.exe",
-            OperatingSystem::MacOS => "MacOS/Unity",
-            OperatingSystem::Linux => "Unity",
-        });
-        if unity_exe_path.try_exists().is_err() || !unity_exe_path.is_file() {
+        let unity_exe_path = get_unity_exe_path(os, &editor_path);
+        let exe_exists = match unity_exe_path.try_exists() {
+            Ok(exe_exists) => exe_exists,
+            Err(err) => {
+                unreadable_editors.push(scan_failed(&editor_path, err));
+                continue;
+            }
+        };
+        if !exe_exists || !unity_exe_path.is_file() {
             continue;
         }
 
-        // TODO: actual IO errors
-        let version_id = editor_path
-            .file_name()
-            .expect("editor version should be a valid string")
-            .to_str()
-            .expect("editor version should unicode-decodeable")
-            .into();
+        let Some(version) = editor_path.file_name().and_then(|name| name.to_str()) else {
+            reporter.warn(&format!(
+                "skipping editor directory {editor_path:?}: its name is not valid unicode"
+            ));
+            continue;
+        };
         installations.push(UnityInstallation {
-            version: version_id,
+            version: normalize_version(version),
             path: editor_path,
         });
     }
 
+    // An unreadable editor only fails the scan if it leaves nothing to choose from
+    if installations.is_empty() && !unreadable_editors.is_empty() {
+        return Err(unreadable_editors.swap_remove(0));
+    }
+    for err in unreadable_editors {
+        reporter.warn(&format!("{err}; skipping it"));
+    }
     Ok(installations)
 }
 
-/// Parses unity-generated `ProjectVersion.txt` files
-pub(crate) fn parse_project_version_file(file: &str) -> Result<String, AppError> {
+/// Normalizes the casing of a Unity version string.
+/// Unity spells release channels in lowercase (`a`/`b`/`f`/`p`), but case-insensitive
+/// filesystems (e.g. macOS) happily keep editor directories named `2022.3.11F1`.
+pub(crate) fn normalize_version(version: &str) -> String {
+    version.to_ascii_lowercase()
+}
+
+/// Parses a roots file: one install root per line, ignoring blank lines and `#` comments.
+/// A leading `~/` is expanded to the user's home directory.
+pub(crate) fn parse_roots_file(contents: &str) -> Vec<PathBuf> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(
+            |line| match (line.strip_prefix("~/"), std::env::var_os("HOME")) {
+                (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+                _ => PathBuf::from(line),
+            },
+        )
+        .collect()
+}
+
+/// Reads the extra install roots listed in `roots_file`, or in the file named by `UYAMLT_ROOTS_FILE`
+pub(crate) fn get_extra_roots(roots_file: Option<&Path>) -> Result<Vec<PathBuf>, AppError> {
+    let roots_file = match roots_file {
+        Some(roots_file) => roots_file.to_path_buf(),
+        None => match std::env::var_os("UYAMLT_ROOTS_FILE") {
+            Some(roots_file) => PathBuf::from(roots_file),
+            None => return Ok(Vec::new()),
+        },
+    };
+
+    let contents = std::fs::read_to_string(&roots_file)
+        .map_err(|_| AppError::RootsFileUnreadable(roots_file.display().to_string()))?;
+    Ok(parse_roots_file(&contents))
+}
+
+/// Editor version information read from a project's `ProjectVersion.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectVersion {
+    pub version: String,
+    /// The editor's changeset, if the file has an `m_EditorVersionWithRevision` key
+    pub revision: Option<String>,
+}
+
+/// Parses unity-generated `ProjectVersion.txt` files.
+/// When both version keys are present, `m_EditorVersionWithRevision` is preferred.
+pub(crate) fn parse_project_version_file(
+    file: &str,
+    reporter: &Reporter,
+) -> Result<ProjectVersion, AppError> {
     const SEPERATOR: &str = "m_EditorVersion: ";
-    let line = file
+    const REVISION_SEPERATOR: &str = "m_EditorVersionWithRevision: ";
+
+    let plain_version = file
+        .lines()
+        .find_map(|s| s.strip_prefix(SEPERATOR))
+        .map(|value| normalize_version(value.trim_end()));
+    let with_revision = file
         .lines()
-        .find(|&s| s.starts_with(SEPERATOR))
-        .ok_or(AppError::InvalidProjectVersionFile)?;
+        .find_map(|s| s.strip_prefix(REVISION_SEPERATOR))
+        .map(str::trim_end)
+        .map(|value| match value.split_once(" (") {
+            Some((version, revision)) => (
+                normalize_version(version),
+                Some(revision.trim_end_matches(')').to_owned()),
+            ),
+            None => (normalize_version(value), None),
+        });
 
-    Ok(line[SEPERATOR.len()..].to_owned())
+    match (plain_version, with_revision) {
+        (Some(version), None) => Ok(ProjectVersion {
+            version,
+            revision: None,
+        }),
+        (plain_version, Some((version, revision))) => {
+            if let Some(plain_version) = plain_version.filter(|plain| *plain != version) {
+                reporter.warn(&format!(
+                    "ProjectVersion.txt disagrees with itself ({plain_version} vs {version}), using {version}"
+                ));
+            }
+            Ok(ProjectVersion { version, revision })
+        }
+        (None, None) => Err(AppError::InvalidProjectVersionFile),
+    }
 }
 
-/// Locate a unity-generated `ProjectVersion.txt` files
-/// TODO test
+/// Locate a unity-generated `ProjectVersion.txt` files.
+/// A `ProjectSettings/ProjectVersionOverride.txt` (same format) takes precedence when present.
 pub(crate) fn locate_project_version_file(workdir: &Path) -> Result<PathBuf, AppError> {
-    let version_file = workdir.join("ProjectSettings/ProjectVersion.txt");
+    ["ProjectVersionOverride.txt", "ProjectVersion.txt"]
+        .iter()
+        .map(|file_name| workdir.join("ProjectSettings").join(file_name))
+        .find(|version_file| version_file.is_file())
+        .ok_or(AppError::ProjectVersionFileNotFound)
+}
 
-    if !version_file.exists() || !version_file.is_file() {
-        Err(AppError::ProjectVersionFileNotFound)
-    } else {
-        Ok(version_file)
+/// Decodes the contents of a `ProjectVersion.txt` file.
+/// UTF-8 is expected, but UTF-16 files are accepted when they start with a byte order mark.
+pub(crate) fn decode_project_version_file(bytes: &[u8]) -> Result<String, AppError> {
+    let decode_utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).map_err(|_| AppError::ProjectVersionFileUnreadable)
+    };
+
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        _ => String::from_utf8(bytes.to_vec()).map_err(|_| AppError::ProjectVersionFileUnreadable),
     }
 }
 
 /// Read a project's version (via `ProjectVersion.txt`)
-pub(crate) fn read_project_version(workdir: &Path) -> Result<String, AppError> {
+pub(crate) fn read_project_version(
+    workdir: &Path,
+    reporter: &Reporter,
+) -> Result<ProjectVersion, AppError> {
     let version_file = locate_project_version_file(workdir)?;
-    let contents = std::fs::read_to_string(version_file)
-        .map_err(|_| AppError::ProjectVersionFileUnreadable)?;
+    let contents =
+        std::fs::read(version_file).map_err(|_| AppError::ProjectVersionFileUnreadable)?;
 
-    parse_project_version_file(&contents)
+    parse_project_version_file(&decode_project_version_file(&contents)?, reporter)
 }
 
-/// Select the most appropriate installation
-pub(crate) fn choose_best_installation<'a>(
-    workdir: &'a Path,
-    installations: &'a UnityInstallationCollection,
-) -> Result<&'a UnityInstallation, AppError> {
+/// An installation picked for a project, along with why it was picked
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Selection {
+    pub installation: UnityInstallation,
+    /// Why `installation` was picked
+    pub source: SelectionSource,
+    /// The project version read while selecting, if `workdir` is a project that pins one
+    pub project_version: Option<ProjectVersion>,
+}
+
+/// Select the most appropriate installation, along with the reason it was selected.
+/// With `allow_patch_fallback`, a project whose exact version is missing gets the newest
+/// installed final release of the same `year.stream` instead of an error.
+pub(crate) fn choose_best_installation(
+    workdir: &Path,
+    installations: &UnityInstallationCollection,
+    allow_patch_fallback: bool,
+    reporter: &Reporter,
+) -> Result<Selection, AppError> {
+    if installations.is_empty() {
+        return Err(AppError::NoUnityInstallations);
+    }
+
     // try reading it from current directory
-    println!("Attempting to probe CWD as project...");
-    let project_editor_version = read_project_version(workdir);
+    let project_editor_version = read_project_version(workdir, reporter);
     match project_editor_version {
-        Ok(version) => return Ok(installations.iter().find(|x| x.version == version).unwrap()),
-        Err(AppError::ProjectVersionFileNotFound) => {
-            println!("CWD is not a project, choosing latest version...");
+        Ok(project_version) if project_version.version.trim().is_empty() => {
+            reporter.warn("ProjectVersion.txt is malformed (empty editor version), choosing latest version...");
+        }
+        Ok(project_version) => {
+            let selected = installations
+                .iter()
+                .find(|x| x.version == project_version.version)
+                .map(|installation| (installation, SelectionSource::ProjectVersion))
+                .or_else(|| {
+                    allow_patch_fallback
+                        .then(|| find_patch_fallback(&project_version.version, installations))
+                        .flatten()
+                        .map(|installation| (installation, SelectionSource::PatchFallback))
+                });
+            return match selected {
+                Some((installation, source)) => Ok(Selection {
+                    installation: installation.clone(),
+                    source,
+                    project_version: Some(project_version),
+                }),
+                None => {
+                    let closest =
+                        find_closest_installation(&project_version.version, installations)
+                            .map(|installation| installation.version.clone());
+                    Err(AppError::RequiredVersionNotInstalled(
+                        project_version.version,
+                        closest,
+                    ))
+                }
+            };
         }
+        Err(AppError::ProjectVersionFileNotFound) => {}
         Err(err) => return Err(err),
     };
 
-    // Choose latest installation instead
+    // Choose latest installation instead; unparseable versions sort first.
+    // `max_by_key` parses each version once, and keeps the last of equal versions.
     installations
         .iter()
-        .sorted_by_key(|&x| &x.version)
-        .next_back()
+        .max_by_key(|&x| x.version.parse::<UnityVersion>().ok())
+        .map(|installation| Selection {
+            installation: installation.clone(),
+            source: SelectionSource::Latest,
+            project_version: None,
+        })
         .ok_or(AppError::NoUnityInstallations)
 }
 
-/// Get the path of `UnityYAMLMerge` for an installation
-pub(crate) fn get_yamltool(os: OperatingSystem, installation: &Path) -> Result<PathBuf, AppError> {
-    let yamltool: PathBuf = match os {
-        OperatingSystem::Windows => installation.join("Editor/Data/Tools/UnityYAMLMerge.exe"),
-        OperatingSystem::MacOS => todo!(),
-        OperatingSystem::Linux => todo!(),
-    };
+/// Pairs each installation with its parsed version, skipping those whose version doesn't parse
+fn with_parsed_versions(
+    installations: &UnityInstallationCollection,
+) -> impl Iterator<Item = (&UnityInstallation, UnityVersion)> {
+    installations
+        .iter()
+        .filter_map(|installation| Some((installation, installation.version.parse().ok()?)))
+}
 
-    if !yamltool.exists() {
-        return Err(AppError::YamlToolNotFound);
-    }
+/// Finds the installation closest to `version` within the same `year.stream`.
+/// Ties between an older and a newer patch go to the newer one.
+pub(crate) fn find_closest_installation<'a>(
+    version: &str,
+    installations: &'a UnityInstallationCollection,
+) -> Option<&'a UnityInstallation> {
+    let version: UnityVersion = version.parse().ok()?;
+    with_parsed_versions(installations)
+        .filter(|(_, candidate)| {
+            candidate.year == version.year && candidate.stream == version.stream
+        })
+        .min_by_key(|(_, candidate)| {
+            (
+                candidate.patch.abs_diff(version.patch),
+                std::cmp::Reverse(*candidate),
+            )
+        })
+        .map(|(installation, _)| installation)
+}
 
-    Ok(yamltool)
+/// Finds the newest installed final release sharing `version`'s `year.stream`
+pub(crate) fn find_patch_fallback<'a>(
+    version: &str,
+    installations: &'a UnityInstallationCollection,
+) -> Option<&'a UnityInstallation> {
+    let version: UnityVersion = version.parse().ok()?;
+    with_parsed_versions(installations)
+        .filter(|(_, candidate)| {
+            candidate.year == version.year
+                && candidate.stream == version.stream
+                && candidate.channel == ReleaseChannel::Final
+        })
+        .max_by_key(|(_, candidate)| *candidate)
+        .map(|(installation, _)| installation)
 }
 
-/// Runs the executable based on given strings
-// TODO: support "located" installations
-// TODO: maybe support UnityHub alternatives?
-pub fn run(args: &[String]) -> anyhow::Result<i32> {
-    let os = get_current_os()?;
+/// Whether `UYAMLT_ALLOW_PATCH_FALLBACK` enables falling back to a same-stream patch
+pub(crate) fn patch_fallback_enabled() -> bool {
+    env_flag("UYAMLT_ALLOW_PATCH_FALLBACK")
+}
 
-    let installations = get_unityhub_installations(os)?;
-    if installations.is_empty() {
-        return Err(AppError::NoUnityInstallations.into());
-    }
-    for installation in &installations {
-        println!("Installation detected: {installation:?}");
+/// Explains why a required version wasn't discovered in any of the scanned `roots`.
+/// Distinguishes a version directory whose Unity binary was removed from one that doesn't exist.
+/// Directory names are matched the way the scan normalizes them, whatever their casing on disk.
+pub(crate) fn diagnose_missing_installation(
+    os: OperatingSystem,
+    roots: &[PathBuf],
+    version: String,
+    closest: Option<String>,
+) -> AppError {
+    let is_incomplete = |root: &PathBuf| {
+        let Ok(entries) = std::fs::read_dir(root) else {
+            return false;
+        };
+        entries.filter_map(Result::ok).any(|entry| {
+            let editor_path = entry.path();
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| normalize_version(name) == version)
+                && editor_path.is_dir()
+                && !get_unity_exe_path(os, &editor_path).is_file()
+        })
+    };
+    if roots.iter().any(is_incomplete) {
+        AppError::IncompleteInstallation(version)
+    } else {
+        AppError::RequiredVersionNotInstalled(version, closest)
     }
+}
 
-    let workdir = std::env::current_dir().unwrap();
-    println!("Working directory: {workdir:?}");
+/// Select the most appropriate installation, explaining a missing project version as precisely as possible.
+/// A forced installation is always used; it only warns if the project pins another version.
+pub(crate) fn select_installation(
+    os: OperatingSystem,
+    workdir: &Path,
+    discovery: &Discovery,
+    allow_patch_fallback: bool,
+    reporter: &Reporter,
+) -> Result<Selection, AppError> {
+    let (installations, roots) = match discovery {
+        Discovery::Forced(installation) => {
+            let project_version = read_project_version(workdir, reporter).ok();
+            if let Some(project_version) = project_version
+                .as_ref()
+                .filter(|project_version| project_version.version != installation.version)
+            {
+                reporter.warn(&format!(
+                    "this project pins Unity {}, but UYAMLT_EDITOR_PATH forces {}",
+                    project_version.version, installation.version
+                ));
+            }
+            return Ok(Selection {
+                installation: installation.clone(),
+                source: SelectionSource::Forced,
+                project_version,
+            });
+        }
+        Discovery::Scanned {
+            installations,
+            roots,
+        } => (installations, roots),
+    };
 
-    let installation = choose_best_installation(&workdir, &installations)?;
-    println!("Selected installation: {installation:?}");
+    choose_best_installation(workdir, installations, allow_patch_fallback, reporter).map_err(
+        |err| match err {
+            AppError::RequiredVersionNotInstalled(version, closest) => {
+                diagnose_missing_installation(os, roots, version, closest)
+            }
+            err => err,
+        },
+    )
+}
 
-    let yamltool: PathBuf = get_yamltool(os, &installation.path)?;
-    println!("Selected yamltool: {yamltool:?}");
+/// Everything needed to run a merge, resolved without spawning anything
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MergeContext {
+    /// The Unity project being merged in, if the working directory is one
+    pub project_root: Option<PathBuf>,
+    /// The editor version the project pins, if any
+    pub project_version: Option<ProjectVersion>,
+    pub installation: UnityInstallation,
+    /// Location of the installation's `UnityYAMLMerge`
+    pub tool_path: PathBuf,
+    pub source: SelectionSource,
+}
 
-    if std::env::var("UYAMLT_DRY_RUN").is_ok() {
-        return Ok(0);
-    }
+/// Resolves everything needed to merge from `workdir`: its project version,
+/// the installation to use, and that installation's `UnityYAMLMerge`.
+/// Extra install roots are read from `UYAMLT_ROOTS_FILE`.
+pub fn resolve(workdir: &Path) -> Result<MergeContext, AppError> {
+    let reporter = &Reporter::default();
+    let os = get_current_os()?;
+    let discovery = get_installations(os, &get_extra_roots(None)?, false, reporter)?;
+    resolve_with(os, workdir, &discovery, patch_fallback_enabled(), reporter)
+}
 
-    println!("passing through...");
-    let process_result = std::process::Command::new(std::fs::canonicalize(yamltool).unwrap())
-        .args(args)
-        .spawn()?
-        .wait()?;
+/// Discovers every installed editor, the same way `uyamlt` itself does.
+/// Honors `UYAMLT_EDITOR_PATH`, `UYAMLT_EDITOR_ROOT` and `UYAMLT_ROOTS_FILE`.
+/// Prints no progress output, but problems it works around (such as a skipped install root)
+/// are still written to stderr as warnings.
+pub fn discover_installations() -> Result<Vec<UnityInstallation>, AppError> {
+    let reporter = &Reporter::default();
+    Ok(
+        get_installations(get_current_os()?, &get_extra_roots(None)?, false, reporter)?
+            .into_installations(),
+    )
+}
 
-    Ok(process_result.code().expect("Process terminated by signal"))
+/// Picks the installation `uyamlt` would use for the project at `workdir`, and why.
+/// Falls back to the latest installation when `workdir` isn't a project.
+pub fn resolve_for_project(workdir: &Path) -> Result<Selection, AppError> {
+    let reporter = &Reporter::default();
+    let os = get_current_os()?;
+    let discovery = get_installations(os, &get_extra_roots(None)?, false, reporter)?;
+    select_installation(os, workdir, &discovery, patch_fallback_enabled(), reporter)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// Resolves a [`MergeContext`] from already discovered installations
+pub(crate) fn resolve_with(
+    os: OperatingSystem,
+    workdir: &Path,
+    discovery: &Discovery,
+    allow_patch_fallback: bool,
+    reporter: &Reporter,
+) -> Result<MergeContext, AppError> {
+    let Selection {
+        installation,
+        source,
+        project_version,
+    } = select_installation(os, workdir, discovery, allow_patch_fallback, reporter)?;
+    let tool_path = get_yamltool(os, &installation.path)?;
 
-    #[test]
-    fn test_parse_project_version_minimal() {
-        let minimal_project_version_file = r#"m_EditorVersion: 2022.3.11f1
-"#;
-        let result = parse_project_version_file(minimal_project_version_file);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "2022.3.11f1");
+    Ok(MergeContext {
+        project_root: project_version.as_ref().map(|_| workdir.to_path_buf()),
+        project_version,
+        installation,
+        tool_path,
+        source,
+    })
+}
+
+/// Prints the outcome of `uyamlt verify` and returns its exit code
+pub(crate) fn report_verification(result: Result<String, AppError>) -> i32 {
+    match result {
+        Ok(summary) => {
+            println!("verify: OK ({summary})");
+            0
+        }
+        Err(err) => {
+            eprintln!("verify: FAILED: {err}");
+            1
+        }
     }
+}
 
-    #[test]
-    fn test_parse_project_version_actual() {
-        let sample_project_version_file = r#"m_EditorVersion: 2022.3.11f1
-m_EditorVersionWithRevision: 2022.3.11f1 (d00248457e15)
-"#;
-        let result = parse_project_version_file(sample_project_version_file);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "2022.3.11f1");
+/// Get where `UnityYAMLMerge` lives inside an installation, whether or not it exists
+pub(crate) fn get_yamltool_path(os: OperatingSystem, installation: &Path) -> PathBuf {
+    installation.join(match os {
+        OperatingSystem::Windows => "Editor/Data/Tools/UnityYAMLMerge.exe",
+        OperatingSystem::MacOS => "Unity.app/Contents/Tools/UnityYAMLMerge",
+        OperatingSystem::Linux => "Editor/Data/Tools/UnityYAMLMerge",
+    })
+}
+
+/// Get the path of `UnityYAMLMerge` for an installation
+pub(crate) fn get_yamltool(os: OperatingSystem, installation: &Path) -> Result<PathBuf, AppError> {
+    let yamltool = get_yamltool_path(os, installation);
+    if !yamltool.exists() {
+        return Err(AppError::YamlToolNotFound);
     }
 
-    #[test]
-    fn test_parse_project_version_invalid() {
-        let invalid_project_file = r#"pver: 2022.3.11f1 (d00248457e15)
-"#;
-        let result = parse_project_version_file(invalid_project_file);
-        assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), AppError::InvalidProjectVersionFile);
+    Ok(yamltool)
+}
+
+/// A discovered installation along with the state of its `UnityYAMLMerge` tool
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct InstallationInfo {
+    pub version: String,
+    pub path: PathBuf,
+    /// Location of `UnityYAMLMerge`, if it exists
+    pub tool_path: Option<PathBuf>,
+    /// Whether `UnityYAMLMerge` exists and can be executed
+    pub tool_executable: bool,
+}
+
+/// Lists every discovered installation, validating each one's `UnityYAMLMerge` tool
+pub fn inventory() -> Result<Vec<InstallationInfo>, AppError> {
+    Ok(inventory_of(get_current_os()?, &discover_installations()?))
+}
+
+pub(crate) fn inventory_of(
+    os: OperatingSystem,
+    installations: &UnityInstallationCollection,
+) -> Vec<InstallationInfo> {
+    installations
+        .iter()
+        .map(|installation| {
+            let tool_path = get_yamltool(os, &installation.path).ok();
+            InstallationInfo {
+                version: installation.version.clone(),
+                path: installation.path.clone(),
+                tool_executable: tool_path.as_deref().is_some_and(is_executable),
+                tool_path,
+            }
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// How much of the child's environment `--debug-env` reveals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DebugEnv {
+    /// Values of sensitive-looking variables are hidden
+    Redacted,
+    /// Every value is printed as-is
+    Full,
+}
+
+/// Options consumed by uyamlt itself rather than forwarded to `UnityYAMLMerge`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Options {
+    /// Append the selected editor to the file named by `$GITHUB_OUTPUT`
+    pub github_output: bool,
+    /// Extra arguments injected into every `UnityYAMLMerge` invocation
+    pub tool_args: Vec<String>,
+    /// Log the environment and working directory the child is spawned with
+    pub debug_env: Option<DebugEnv>,
+    /// File listing extra install roots to scan
+    pub roots_file: Option<PathBuf>,
+    /// CI annotation syntax to render warnings in
+    pub annotations: Annotations,
+    /// Scan install roots concurrently
+    pub parallel_scan: bool,
+    /// Print a summary line to stderr once the merge tool exits
+    pub summary: bool,
+    /// Progress output requested via `-v` (info) or `-vv` (debug)
+    pub verbosity: Verbosity,
+    /// Print the resolved `UnityYAMLMerge` invocation instead of running it
+    pub dry_run: bool,
+    /// Fall back to a same-stream patch when the project's exact version is missing
+    pub allow_patch_fallback: bool,
+}
+
+/// Splits uyamlt's own flags out of the arguments meant for `UnityYAMLMerge`.
+/// `args` is the full argv; the program name (if present at all) is never forwarded.
+/// uyamlt's flags must come first: everything from the first other argument (usually the
+/// subcommand), or after a `--`, is forwarded untouched, so a merged file named e.g. `-v` survives.
+///
+/// Git's `%O`, `%A`, `%B` and `%P` merge driver placeholders are forwarded as plain
+/// positional arguments. `UnityYAMLMerge` has no use for the conflict marker size (`%L`),
+/// so it can be passed as `--marker-size %L` ahead of the subcommand and is consumed here.
+pub(crate) fn parse_args(args: &[String]) -> Result<(Options, Vec<String>), AppError> {
+    let mut options = Options::default();
+    let mut forwarded = Vec::new();
+
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--github-output" => options.github_output = true,
+            "--parallel-scan" => options.parallel_scan = true,
+            "--summary" => options.summary = true,
+            "--dry-run" => options.dry_run = true,
+            "--allow-patch-fallback" => options.allow_patch_fallback = true,
+            "-v" | "--verbose" if options.verbosity == Verbosity::Quiet => {
+                options.verbosity = Verbosity::Info;
+            }
+            "-v" | "--verbose" | "-vv" => options.verbosity = Verbosity::Debug,
+            "--debug-env" => options.debug_env = Some(DebugEnv::Redacted),
+            "--debug-env=full" => options.debug_env = Some(DebugEnv::Full),
+            "--tool-arg" => options.tool_args.push(
+                args.next()
+                    .ok_or_else(|| AppError::MissingArgumentValue(arg.clone()))?
+                    .clone(),
+            ),
+            "--roots-file" => {
+                options.roots_file =
+                    Some(PathBuf::from(args.next().ok_or_else(|| {
+                        AppError::MissingArgumentValue(arg.clone())
+                    })?));
+            }
+            "--annotations" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| AppError::MissingArgumentValue(arg.clone()))?;
+                options.annotations = match value.as_str() {
+                    "github" => Annotations::GitHub,
+                    "none" => Annotations::None,
+                    _ => return Err(AppError::InvalidArgumentValue(arg.clone(), value.clone())),
+                };
+            }
+            "--marker-size" => {
+                args.next()
+                    .ok_or_else(|| AppError::MissingArgumentValue(arg.clone()))?;
+            }
+            "--" => break,
+            _ => {
+                forwarded.push(arg.clone());
+                break;
+            }
+        }
+    }
+    forwarded.extend(args.cloned());
+
+    Ok((options, forwarded))
+}
+
+/// Splits `UYAMLT_TOOL_ARGS` into arguments the way a POSIX shell would.
+/// Single quotes keep everything literal, double quotes only treat `\"` and `\\` as escapes,
+/// and outside of quotes a backslash escapes the next character.
+pub(crate) fn split_tool_args(value: &str) -> Result<Vec<String>, AppError> {
+    let invalid =
+        || AppError::InvalidArgumentValue("UYAMLT_TOOL_ARGS".to_owned(), value.to_owned());
+
+    let mut args = Vec::new();
+    let mut arg: Option<String> = None;
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => args.extend(arg.take()),
+            '\'' => {
+                let arg = arg.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or_else(invalid)? {
+                        '\'' => break,
+                        c => arg.push(c),
+                    }
+                }
+            }
+            '"' => {
+                let arg = arg.get_or_insert_with(String::new);
+                loop {
+                    match chars.next().ok_or_else(invalid)? {
+                        '"' => break,
+                        '\\' => match chars.next().ok_or_else(invalid)? {
+                            escaped @ ('"' | '\\') => arg.push(escaped),
+                            c => {
+                                arg.push('\\');
+                                arg.push(c);
+                            }
+                        },
+                        c => arg.push(c),
+                    }
+                }
+            }
+            '\\' => arg
+                .get_or_insert_with(String::new)
+                .push(chars.next().ok_or_else(invalid)?),
+            c => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(arg);
+
+    Ok(args)
+}
+
+/// Builds the `UnityYAMLMerge` invocation.
+/// Injected tool args go right after the subcommand (e.g. `merge`),
+/// ahead of the forwarded options and git's placeholder paths.
+pub(crate) fn build_command(
+    yamltool: &Path,
+    tool_args: &[String],
+    forwarded: &[String],
+) -> std::process::Command {
+    let subcommand_len = usize::from(forwarded.first().is_some_and(|arg| !arg.starts_with('-')));
+    let (subcommand, rest) = forwarded.split_at(subcommand_len);
+
+    let mut command = std::process::Command::new(yamltool);
+    command.args(subcommand).args(tool_args).args(rest);
+    command
+}
+
+/// Renders the environment and working directory a child process is spawned with.
+/// In [`DebugEnv::Redacted`] mode, values of variables whose names look like secrets are hidden.
+pub(crate) fn format_child_env(
+    vars: impl Iterator<Item = (OsString, OsString)>,
+    workdir: &Path,
+    mode: DebugEnv,
+) -> String {
+    const SENSITIVE_MARKERS: [&str; 6] =
+        ["TOKEN", "SECRET", "PASSWORD", "KEY", "CREDENTIAL", "AUTH"];
+
+    let mut dump = format!("working directory: {}\n", workdir.display());
+    for (name, value) in vars.sorted() {
+        let name = name.to_string_lossy();
+        let is_sensitive = SENSITIVE_MARKERS
+            .iter()
+            .any(|marker| name.to_ascii_uppercase().contains(marker));
+        if mode == DebugEnv::Redacted && is_sensitive {
+            dump.push_str(&format!("{name}=<redacted>\n"));
+        } else {
+            dump.push_str(&format!("{name}={}\n", value.to_string_lossy()));
+        }
+    }
+
+    dump
+}
+
+/// Renders what a dry run would have done: the selected installation, its tool, and the exact argv
+pub(crate) fn format_dry_run(context: &MergeContext, command: &std::process::Command) -> String {
+    let argv = std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| format!("{arg:?}"))
+        .join(" ");
+    format!(
+        "installation: {} ({})\ntool: {}\ncommand: {argv}\n",
+        context.installation.version,
+        context.installation.path.display(),
+        context.tool_path.display(),
+    )
+}
+
+/// Renders the summary line printed after a merge.
+/// The merged file is the last forwarded argument (the output path in git's merge tool/driver commands).
+pub(crate) fn format_summary(forwarded: &[String], version: &str, code: i32) -> String {
+    match forwarded.last() {
+        Some(file) if forwarded.len() > 1 => {
+            format!("uyamlt: merged {file} with {version} (exit {code})")
+        }
+        _ => format!("uyamlt: ran UnityYAMLMerge from {version} (exit {code})"),
+    }
+}
+
+/// Appends the selected editor as GitHub Actions step outputs
+pub(crate) fn write_github_output(
+    output_file: &Path,
+    installation: &UnityInstallation,
+    yamltool: &Path,
+) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(output_file)?;
+    writeln!(file, "selected_version={}", installation.version)?;
+    writeln!(file, "tool_path={}", yamltool.display())
+}
+
+/// Runs the executable based on given strings (the full argv, including the program name).
+/// Nothing is ever written into installation directories, so read-only editor images work.
+// TODO: maybe support UnityHub alternatives?
+pub fn run(args: &[String]) -> anyhow::Result<i32> {
+    let (options, args) = parse_args(args)?;
+    let env_verbosity =
+        std::env::var("UYAMLT_VERBOSE").map_or(Verbosity::Quiet, |value| parse_verbosity(&value));
+    let reporter = &Reporter {
+        annotations: options.annotations,
+        verbosity: options.verbosity.max(env_verbosity),
+    };
+    let os = get_current_os()?;
+    let extra_roots = get_extra_roots(options.roots_file.as_deref())?;
+    let allow_patch_fallback = options.allow_patch_fallback || patch_fallback_enabled();
+
+    if args.first().is_some_and(|arg| arg == "verify") {
+        let workdir = std::env::current_dir()?;
+        let result = get_installations(os, &extra_roots, options.parallel_scan, reporter).and_then(
+            |discovery| {
+                resolve_with(os, &workdir, &discovery, allow_patch_fallback, reporter).map(
+                    |context| {
+                        format!(
+                            "Unity {} with {:?}",
+                            context.installation.version, context.tool_path
+                        )
+                    },
+                )
+            },
+        );
+        return Ok(report_verification(result));
+    }
+
+    let discovery = get_installations(os, &extra_roots, options.parallel_scan, reporter)?;
+    if discovery.installations().is_empty() {
+        return Err(AppError::NoUnityInstallations.into());
+    }
+    for installation in discovery.installations() {
+        reporter.log(
+            Verbosity::Debug,
+            &format!("Installation detected: {installation:?}"),
+        );
+    }
+
+    let workdir = std::env::current_dir().unwrap();
+    reporter.log(Verbosity::Debug, &format!("Working directory: {workdir:?}"));
+
+    let context = resolve_with(os, &workdir, &discovery, allow_patch_fallback, reporter)?;
+    if let (SelectionSource::PatchFallback, Some(project_version)) =
+        (context.source, &context.project_version)
+    {
+        reporter.warn(&format!(
+            "Unity {} is not installed, falling back to {}",
+            project_version.version, context.installation.version
+        ));
+    }
+    if context.source == SelectionSource::Latest {
+        reporter.log(
+            Verbosity::Info,
+            "CWD is not a project, choosing latest version...",
+        );
+    }
+    reporter.log(
+        Verbosity::Info,
+        &format!(
+            "Selected installation ({}): {:?}",
+            context.source, context.installation
+        ),
+    );
+    reporter.log(
+        Verbosity::Info,
+        &format!("Selected yamltool: {:?}", context.tool_path),
+    );
+
+    if options.github_output {
+        match std::env::var_os("GITHUB_OUTPUT") {
+            Some(output_file) => {
+                write_github_output(
+                    Path::new(&output_file),
+                    &context.installation,
+                    &context.tool_path,
+                )?;
+            }
+            None => reporter.warn("--github-output was given but $GITHUB_OUTPUT is not set"),
+        }
+    }
+
+    let mut tool_args = split_tool_args(&std::env::var("UYAMLT_TOOL_ARGS").unwrap_or_default())?;
+    tool_args.extend(options.tool_args);
+
+    if let Some(mode) = options.debug_env {
+        eprint!(
+            "Debug: child environment\n{}",
+            format_child_env(std::env::vars_os(), &workdir, mode)
+        );
+    }
+
+    let mut command = build_command(
+        &std::fs::canonicalize(&context.tool_path)?,
+        &tool_args,
+        &args,
+    );
+    if options.dry_run || env_flag("UYAMLT_DRY_RUN") {
+        print!("{}", format_dry_run(&context, &command));
+        return Ok(0);
+    }
+
+    reporter.log(Verbosity::Debug, "passing through...");
+    let process_result = command.spawn()?.wait()?;
+
+    let code = process_result.code().expect("Process terminated by signal");
+    if options.summary || env_flag("UYAMLT_SUMMARY") {
+        eprintln!(
+            "{}",
+            format_summary(&args, &context.installation.version, code)
+        );
+    }
+
+    Ok(code)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Creates a fresh, empty directory for a test fixture
+    pub(crate) fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("uyamlt-test-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Creates a project fixture pinned to the given editor version
+    fn project_fixture(name: &str, version: &str) -> PathBuf {
+        let dir = fixture_dir(name);
+        std::fs::create_dir_all(dir.join("ProjectSettings")).unwrap();
+        std::fs::write(
+            dir.join("ProjectSettings/ProjectVersion.txt"),
+            format!("m_EditorVersion: {version}\n"),
+        )
+        .unwrap();
+        dir
+    }
+
+    /// Creates a `version`-named editor install under `root`, laid out the way `os` lays out editors,
+    /// optionally with its `UnityYAMLMerge`
+    fn editor_fixture(
+        root: &Path,
+        version: impl AsRef<Path>,
+        os: OperatingSystem,
+        with_tool: bool,
+    ) -> PathBuf {
+        let editor_path = root.join(version);
+        let mut files = vec![get_unity_exe_path(os, &editor_path)];
+        if with_tool {
+            files.push(get_yamltool_path(os, &editor_path));
+        }
+        for file in files {
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, "").unwrap();
+        }
+        editor_path
+    }
+
+    /// Wraps installations as if they were scanned from no particular root
+    fn scanned(installations: UnityInstallationCollection) -> Discovery {
+        Discovery::Scanned {
+            installations,
+            roots: Vec::new(),
+        }
+    }
+
+    fn installations_of(versions: &[&str]) -> UnityInstallationCollection {
+        versions
+            .iter()
+            .map(|&version| UnityInstallation {
+                version: version.to_owned(),
+                path: PathBuf::from(version),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_project_version_minimal() {
+        let minimal_project_version_file = r#"m_EditorVersion: 2022.3.11f1
+"#;
+        let result = parse_project_version_file(minimal_project_version_file, &Reporter::default());
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().version, "2022.3.11f1");
+    }
+
+    #[test]
+    fn test_parse_project_version_actual() {
+        let sample_project_version_file = r#"m_EditorVersion: 2022.3.11f1
+m_EditorVersionWithRevision: 2022.3.11f1 (d00248457e15)
+"#;
+        let result = parse_project_version_file(sample_project_version_file, &Reporter::default());
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            ProjectVersion {
+                version: "2022.3.11f1".to_owned(),
+                revision: Some("d00248457e15".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_project_version_trailing_whitespace() {
+        let padded_project_version_file = "m_EditorVersion: 2022.3.11f1  \t\n";
+        let result = parse_project_version_file(padded_project_version_file, &Reporter::default());
+        assert_eq!(result.unwrap().version, "2022.3.11f1");
+    }
+
+    #[test]
+    fn test_parse_project_version_crlf() {
+        let crlf_project_version_file =
+            "m_EditorVersion: 2022.3.11f1\r\nm_EditorVersionWithRevision: 2022.3.11f1 (d00248457e15) \r\n";
+        let result =
+            parse_project_version_file(crlf_project_version_file, &Reporter::default()).unwrap();
+        assert_eq!(result.version, "2022.3.11f1");
+        assert_eq!(result.revision.as_deref(), Some("d00248457e15"));
+    }
+
+    #[test]
+    fn test_parse_project_version_lone_carriage_return() {
+        let result =
+            parse_project_version_file("m_EditorVersion: 2022.3.11f1\r", &Reporter::default());
+        assert_eq!(result.unwrap().version, "2022.3.11f1");
+    }
+
+    #[test]
+    fn test_read_project_version_utf16le_bom() {
+        let workdir = fixture_dir("utf16le-project-version");
+        std::fs::create_dir_all(workdir.join("ProjectSettings")).unwrap();
+        let mut contents = vec![0xFF, 0xFE];
+        contents.extend(
+            "m_EditorVersion: 2022.3.11f1\r\n"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes),
+        );
+        std::fs::write(workdir.join("ProjectSettings/ProjectVersion.txt"), contents).unwrap();
+
+        let result = read_project_version(&workdir, &Reporter::default());
+        assert_eq!(result.unwrap().version, "2022.3.11f1");
+    }
+
+    #[test]
+    fn test_parse_project_version_prefers_revision_line() {
+        let mismatched_project_version_file = r#"m_EditorVersion: 2022.3.10f1
+m_EditorVersionWithRevision: 2022.3.11f1 (d00248457e15)
+"#;
+        let result =
+            parse_project_version_file(mismatched_project_version_file, &Reporter::default());
+        assert_eq!(result.unwrap().version, "2022.3.11f1");
+    }
+
+    #[test]
+    fn test_parse_project_version_invalid() {
+        let invalid_project_file = r#"pver: 2022.3.11f1 (d00248457e15)
+"#;
+        let result = parse_project_version_file(invalid_project_file, &Reporter::default());
+        assert!(result.is_err());
+        assert_eq!(result.err().unwrap(), AppError::InvalidProjectVersionFile);
+    }
+
+    #[test]
+    fn test_choose_best_installation_source_project_version() {
+        let workdir = project_fixture("source-project-version", "2022.3.11f1");
+        let installations = installations_of(&["2021.3.5f1", "2022.3.11f1", "2023.1.0f1"]);
+        let Selection {
+            installation,
+            source,
+            ..
+        } = choose_best_installation(&workdir, &installations, false, &Reporter::default())
+            .unwrap();
+        assert_eq!(installation.version, "2022.3.11f1");
+        assert_eq!(source, SelectionSource::ProjectVersion);
+    }
+
+    #[test]
+    fn test_choose_best_installation_source_latest() {
+        let workdir = fixture_dir("source-latest");
+        let installations = installations_of(&["2021.3.5f1", "2023.1.0f1", "2022.3.11f1"]);
+        let Selection {
+            installation,
+            source,
+            ..
+        } = choose_best_installation(&workdir, &installations, false, &Reporter::default())
+            .unwrap();
+        assert_eq!(installation.version, "2023.1.0f1");
+        assert_eq!(source, SelectionSource::Latest);
+    }
+
+    #[test]
+    fn test_select_installation_source_per_scenario() {
+        let project = project_fixture("source-scenarios", "2022.3.11f1");
+        let not_a_project = fixture_dir("source-scenarios-latest");
+        let exact = scanned(installations_of(&["2022.3.11f1", "2023.1.0f1"]));
+        let same_stream = scanned(installations_of(&["2022.3.12f1", "2023.1.0f1"]));
+        let forced = Discovery::Forced(installations_of(&["2021.3.5f1"]).remove(0));
+
+        for (workdir, discovery, allow_patch_fallback, version, source) in [
+            (
+                &project,
+                &exact,
+                false,
+                "2022.3.11f1",
+                SelectionSource::ProjectVersion,
+            ),
+            (
+                &project,
+                &same_stream,
+                true,
+                "2022.3.12f1",
+                SelectionSource::PatchFallback,
+            ),
+            (
+                &not_a_project,
+                &exact,
+                false,
+                "2023.1.0f1",
+                SelectionSource::Latest,
+            ),
+            (
+                &project,
+                &forced,
+                false,
+                "2021.3.5f1",
+                SelectionSource::Forced,
+            ),
+        ] {
+            let Selection {
+                installation,
+                source: selected_source,
+                ..
+            } = select_installation(
+                OperatingSystem::Linux,
+                workdir,
+                discovery,
+                allow_patch_fallback,
+                &Reporter::default(),
+            )
+            .unwrap();
+            assert_eq!(
+                (installation.version.as_str(), selected_source),
+                (version, source)
+            );
+        }
+    }
+
+    #[test]
+    fn test_editor_root_override_replaces_computed_path() {
+        let editor_root = fixture_dir("editor-root-override");
+        let path = get_installations_path_with_override(
+            OperatingSystem::Linux,
+            Some(editor_root.clone().into_os_string()),
+            &Reporter::default(),
+        );
+        assert_eq!(path.unwrap(), editor_root);
+    }
+
+    #[test]
+    fn test_editor_root_override_ignores_empty_value() {
+        let path = get_installations_path_with_override(
+            OperatingSystem::MacOS,
+            Some(OsString::new()),
+            &Reporter::default(),
+        );
+        assert_eq!(
+            path.unwrap(),
+            PathBuf::from("/Applications/Unity/Hub/Editor")
+        );
+    }
+
+    #[test]
+    fn test_parse_args_strips_own_flags() {
+        let args = ["uyamlt", "--github-output", "merge", "-p", "base"].map(String::from);
+        let (options, forwarded) = parse_args(&args).unwrap();
+        assert!(options.github_output);
+        assert_eq!(forwarded, ["merge", "-p", "base"]);
+    }
+
+    #[test]
+    fn test_parse_args_stops_at_first_forwarded_argument() {
+        let args = ["uyamlt", "merge", "-p", "--summary", "-v"].map(String::from);
+        let (options, forwarded) = parse_args(&args).unwrap();
+        assert_eq!(options, Options::default());
+        assert_eq!(forwarded, ["merge", "-p", "--summary", "-v"]);
+
+        let args = ["uyamlt", "--summary", "--", "--dry-run", "merge"].map(String::from);
+        let (options, forwarded) = parse_args(&args).unwrap();
+        assert!(options.summary && !options.dry_run);
+        assert_eq!(forwarded, ["--dry-run", "merge"]);
+    }
+
+    #[test]
+    fn test_parse_args_verbosity() {
+        let verbosity = |args: &[&str]| {
+            let args: Vec<String> = args.iter().copied().map(String::from).collect();
+            let (options, forwarded) = parse_args(&args).unwrap();
+            assert_eq!(forwarded, ["merge"]);
+            options.verbosity
+        };
+        assert_eq!(verbosity(&["uyamlt", "merge"]), Verbosity::Quiet);
+        assert_eq!(verbosity(&["uyamlt", "-v", "merge"]), Verbosity::Info);
+        assert_eq!(
+            verbosity(&["uyamlt", "-v", "-v", "merge"]),
+            Verbosity::Debug
+        );
+        assert_eq!(verbosity(&["uyamlt", "-vv", "merge"]), Verbosity::Debug);
+        assert_eq!(
+            verbosity(&["uyamlt", "--verbose", "merge"]),
+            Verbosity::Info
+        );
+    }
+
+    #[test]
+    fn test_is_truthy() {
+        for falsy in ["", " ", "0", "false", "FALSE", "no", "Off"] {
+            assert!(!is_truthy(falsy), "{falsy:?}");
+        }
+        for truthy in ["1", "true", "yes", "on", "2"] {
+            assert!(is_truthy(truthy), "{truthy:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_verbosity() {
+        assert_eq!(parse_verbosity(""), Verbosity::Quiet);
+        assert_eq!(parse_verbosity("0"), Verbosity::Quiet);
+        assert_eq!(parse_verbosity("off"), Verbosity::Quiet);
+        assert_eq!(parse_verbosity("1"), Verbosity::Info);
+        assert_eq!(parse_verbosity("true"), Verbosity::Info);
+        assert_eq!(parse_verbosity("2"), Verbosity::Debug);
+        assert_eq!(parse_verbosity("DEBUG"), Verbosity::Debug);
+    }
+
+    #[test]
+    fn test_parse_args_empty_argv() {
+        let (options, forwarded) = parse_args(&[]).unwrap();
+        assert_eq!(options, Options::default());
+        assert!(forwarded.is_empty());
+    }
+
+    #[test]
+    fn test_program_name_not_forwarded() {
+        let args = ["/usr/local/bin/uyamlt", "merge", "-p", "base"].map(String::from);
+        let (options, forwarded) = parse_args(&args).unwrap();
+        let command = build_command(Path::new("UnityYAMLMerge"), &options.tool_args, &forwarded);
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["merge", "-p", "base"]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_merge_driver_placeholders() {
+        let args = [
+            "uyamlt",
+            "--marker-size",
+            "7",
+            "merge",
+            "--force",
+            ".merge_file_a6Xb1c",
+            ".merge_file_Pq3z9d",
+            ".merge_file_Lm0k2e",
+            "-v",
+        ]
+        .map(String::from);
+        let (_, forwarded) = parse_args(&args).unwrap();
+        assert_eq!(
+            forwarded,
+            [
+                "merge",
+                "--force",
+                ".merge_file_a6Xb1c",
+                ".merge_file_Pq3z9d",
+                ".merge_file_Lm0k2e",
+                "-v",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_missing_tool_arg_value() {
+        let args = ["uyamlt", "--tool-arg"].map(String::from);
+        assert_eq!(
+            parse_args(&args).err().unwrap(),
+            AppError::MissingArgumentValue("--tool-arg".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_split_tool_args() {
+        assert_eq!(
+            split_tool_args("  --fallback none ").unwrap(),
+            ["--fallback", "none"]
+        );
+        assert_eq!(
+            split_tool_args(r#"--fallback "C:\Program Files\Merge\merge.exe" -r '/my rules.txt'"#)
+                .unwrap(),
+            [
+                "--fallback",
+                r"C:\Program Files\Merge\merge.exe",
+                "-r",
+                "/my rules.txt"
+            ]
+        );
+        assert_eq!(
+            split_tool_args(r#"a\ b "say \"hi\"" '' x"'"y"#).unwrap(),
+            ["a b", r#"say "hi""#, "", "x'y"]
+        );
+        assert_eq!(
+            split_tool_args("--fallback 'none").err().unwrap(),
+            AppError::InvalidArgumentValue(
+                "UYAMLT_TOOL_ARGS".to_owned(),
+                "--fallback 'none".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn test_build_command_injects_tool_args_before_forwarded() {
+        let args = [
+            "uyamlt",
+            "--tool-arg",
+            "--fallback",
+            "--tool-arg",
+            "none",
+            "merge",
+            "-p",
+            "base",
+            "remote",
+            "local",
+            "merged",
+        ]
+        .map(String::from);
+        let (options, forwarded) = parse_args(&args).unwrap();
+        let command = build_command(Path::new("UnityYAMLMerge"), &options.tool_args, &forwarded);
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            [
+                "merge",
+                "--fallback",
+                "none",
+                "-p",
+                "base",
+                "remote",
+                "local",
+                "merged"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_child_env_redacts_sensitive_names() {
+        let vars = [
+            ("PATH", "/usr/bin"),
+            ("GITHUB_TOKEN", "ghp_secret"),
+            ("Api_Key", "hunter2"),
+        ]
+        .map(|(name, value)| (OsString::from(name), OsString::from(value)));
+
+        let dump = format_child_env(
+            vars.clone().into_iter(),
+            Path::new("/repo"),
+            DebugEnv::Redacted,
+        );
+        assert_eq!(
+            dump,
+            "working directory: /repo\n\
+             Api_Key=<redacted>\n\
+             GITHUB_TOKEN=<redacted>\n\
+             PATH=/usr/bin\n"
+        );
+
+        let dump = format_child_env(vars.into_iter(), Path::new("/repo"), DebugEnv::Full);
+        assert!(dump.contains("GITHUB_TOKEN=ghp_secret\n"));
+    }
+
+    #[test]
+    fn test_parse_args_debug_env() {
+        let args = ["uyamlt", "--debug-env=full", "merge"].map(String::from);
+        let (options, forwarded) = parse_args(&args).unwrap();
+        assert_eq!(options.debug_env, Some(DebugEnv::Full));
+        assert_eq!(forwarded, ["merge"]);
+    }
+
+    #[test]
+    fn test_fallback_warning_github_annotation() {
+        let args = ["uyamlt", "--annotations", "github"].map(String::from);
+        let (options, _) = parse_args(&args).unwrap();
+        assert_eq!(
+            format_annotation(
+                options.annotations,
+                "ProjectVersion.txt is malformed (empty editor version), choosing latest version..."
+            )
+            .unwrap(),
+            "::warning title=uyamlt::ProjectVersion.txt is malformed (empty editor version), choosing latest version..."
+        );
+        assert_eq!(format_annotation(Annotations::None, "anything"), None);
+    }
+
+    #[test]
+    fn test_parse_args_invalid_annotations() {
+        let args = ["uyamlt", "--annotations", "jenkins"].map(String::from);
+        assert_eq!(
+            parse_args(&args).err().unwrap(),
+            AppError::InvalidArgumentValue("--annotations".to_owned(), "jenkins".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let args = [
+            "uyamlt",
+            "--summary",
+            "merge",
+            "-p",
+            "base",
+            "remote",
+            "local",
+            "Assets/Main.unity",
+        ]
+        .map(String::from);
+        let (options, forwarded) = parse_args(&args).unwrap();
+        assert!(options.summary);
+        assert_eq!(
+            format_summary(&forwarded, "2022.3.11f1", 1),
+            "uyamlt: merged Assets/Main.unity with 2022.3.11f1 (exit 1)"
+        );
+        assert_eq!(
+            format_summary(&[], "2022.3.11f1", 0),
+            "uyamlt: ran UnityYAMLMerge from 2022.3.11f1 (exit 0)"
+        );
+    }
+
+    #[test]
+    fn test_write_github_output() {
+        let output_file = fixture_dir("github-output").join("output");
+        std::fs::write(&output_file, "previous=step\n").unwrap();
+
+        let installation = &installations_of(&["2022.3.11f1"])[0];
+        let yamltool = Path::new("/editors/2022.3.11f1/UnityYAMLMerge");
+        write_github_output(&output_file, installation, yamltool).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&output_file).unwrap(),
+            "previous=step\n\
+             selected_version=2022.3.11f1\n\
+             tool_path=/editors/2022.3.11f1/UnityYAMLMerge\n"
+        );
+    }
+
+    #[test]
+    fn test_diagnose_missing_installation_binary_deleted() {
+        let installations_path = fixture_dir("binary-deleted");
+        std::fs::create_dir_all(installations_path.join("2022.3.11f1/Editor/Data/Tools")).unwrap();
+
+        let err = diagnose_missing_installation(
+            OperatingSystem::Linux,
+            std::slice::from_ref(&installations_path),
+            "2022.3.11f1".to_owned(),
+            None,
+        );
+        assert_eq!(
+            err,
+            AppError::IncompleteInstallation("2022.3.11f1".to_owned())
+        );
+        assert!(err.to_string().contains("incomplete or corrupt"));
+    }
+
+    #[test]
+    fn test_diagnose_missing_installation_in_any_scanned_root() {
+        let roots_dir = fixture_dir("binary-deleted-roots");
+        let roots = unityhub_roots(
+            roots_dir.join("hub"),
+            Some(roots_dir.join("secondary")),
+            &[roots_dir.join("share")],
+        );
+        editor_fixture(&roots[0], "2021.3.5f1", OperatingSystem::Linux, true);
+        std::fs::create_dir_all(roots_dir.join("share/2022.3.11f1/Editor/Data/Tools")).unwrap();
+        let discovery = Discovery::Scanned {
+            installations: scan_unityhub_roots(
+                OperatingSystem::Linux,
+                &roots,
+                false,
+                &Reporter::default(),
+            )
+            .unwrap(),
+            roots,
+        };
+        let workdir = project_fixture("binary-deleted-roots-project", "2022.3.11f1");
+
+        let err = select_installation(
+            OperatingSystem::Linux,
+            &workdir,
+            &discovery,
+            false,
+            &Reporter::default(),
+        );
+        assert_eq!(
+            err.err().unwrap(),
+            AppError::IncompleteInstallation("2022.3.11f1".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_diagnose_missing_installation_with_uppercase_directory() {
+        let installations_path = fixture_dir("binary-deleted-uppercase");
+        std::fs::create_dir_all(installations_path.join("2022.3.11F1/Editor/Data/Tools")).unwrap();
+
+        let err = diagnose_missing_installation(
+            OperatingSystem::Linux,
+            std::slice::from_ref(&installations_path),
+            "2022.3.11f1".to_owned(),
+            None,
+        );
+        assert_eq!(
+            err,
+            AppError::IncompleteInstallation("2022.3.11f1".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_diagnose_missing_installation_not_installed() {
+        let installations_path = fixture_dir("not-installed");
+        let err = diagnose_missing_installation(
+            OperatingSystem::Linux,
+            std::slice::from_ref(&installations_path),
+            "2022.3.11f1".to_owned(),
+            None,
+        );
+        assert_eq!(
+            err,
+            AppError::RequiredVersionNotInstalled("2022.3.11f1".to_owned(), None)
+        );
+    }
+
+    #[test]
+    fn test_choose_best_installation_empty_with_project_version() {
+        let workdir = project_fixture("empty-installations", "2022.3.11f1");
+        let installations = UnityInstallationCollection::new();
+        let result =
+            choose_best_installation(&workdir, &installations, false, &Reporter::default());
+        assert_eq!(result.err().unwrap(), AppError::NoUnityInstallations);
+    }
+
+    #[test]
+    fn test_inventory_reports_tool_status() {
+        let installations_path = fixture_dir("inventory");
+        let with_tool = editor_fixture(
+            &installations_path,
+            "2022.3.11f1",
+            OperatingSystem::Windows,
+            true,
+        );
+        let yamltool = get_yamltool_path(OperatingSystem::Windows, &with_tool);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let permissions = std::fs::Permissions::from_mode(0o755);
+            std::fs::set_permissions(&yamltool, permissions).unwrap();
+        }
+        let without_tool = editor_fixture(
+            &installations_path,
+            "2021.3.5f1",
+            OperatingSystem::Windows,
+            false,
+        );
+
+        let installations = vec![
+            UnityInstallation {
+                version: "2022.3.11f1".to_owned(),
+                path: with_tool,
+            },
+            UnityInstallation {
+                version: "2021.3.5f1".to_owned(),
+                path: without_tool,
+            },
+        ];
+        let inventory = inventory_of(OperatingSystem::Windows, &installations);
+
+        assert_eq!(inventory[0].tool_path.as_deref(), Some(yamltool.as_path()));
+        assert!(inventory[0].tool_executable);
+        assert_eq!(inventory[1].tool_path, None);
+        assert!(!inventory[1].tool_executable);
+    }
+
+    #[test]
+    fn test_choose_best_installation_empty_project_version() {
+        let workdir = project_fixture("empty-project-version", " ");
+        let installations = installations_of(&["2021.3.5f1", "2022.3.11f1"]);
+        let Selection {
+            installation,
+            source,
+            ..
+        } = choose_best_installation(&workdir, &installations, false, &Reporter::default())
+            .unwrap();
+        assert_eq!(installation.version, "2022.3.11f1");
+        assert_eq!(source, SelectionSource::Latest);
+    }
+
+    #[test]
+    fn test_verify_fails_for_unmatched_project_version() {
+        let workdir = project_fixture("verify-unmatched", "2022.3.11f1");
+        let installations = installations_of(&["2021.3.5f1"]);
+        let result = resolve_with(
+            OperatingSystem::Windows,
+            &workdir,
+            &scanned(installations.clone()),
+            false,
+            &Reporter::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(AppError::RequiredVersionNotInstalled(..))
+        ));
+    }
+
+    #[test]
+    fn test_verify_succeeds_when_requirements_met() {
+        let workdir = project_fixture("verify-ok", "2022.3.11f1");
+        let editor_path = editor_fixture(
+            &fixture_dir("verify-ok-editors"),
+            "2022.3.11f1",
+            OperatingSystem::Windows,
+            true,
+        );
+        let installations = vec![UnityInstallation {
+            version: "2022.3.11f1".to_owned(),
+            path: editor_path.clone(),
+        }];
+
+        let context = resolve_with(
+            OperatingSystem::Windows,
+            &workdir,
+            &scanned(installations.clone()),
+            false,
+            &Reporter::default(),
+        )
+        .unwrap();
+        assert_eq!(context.installation.version, "2022.3.11f1");
+        assert_eq!(
+            context.tool_path,
+            editor_path.join("Editor/Data/Tools/UnityYAMLMerge.exe")
+        );
+    }
+
+    #[test]
+    fn test_roots_file_scans_listed_roots() {
+        let roots_dir = fixture_dir("roots-file");
+        for (root, version) in [
+            ("ssd", "2022.3.11f1"),
+            ("share", "2021.3.5f1"),
+            ("old", "2019.4.0f1"),
+        ] {
+            editor_fixture(
+                &roots_dir.join(root),
+                version,
+                OperatingSystem::Linux,
+                false,
+            );
+        }
+        let roots_file = format!(
+            "# managed by ops\n{}\n\n# {}\n{}\n",
+            roots_dir.join("ssd").display(),
+            roots_dir.join("old").display(),
+            roots_dir.join("share").display(),
+        );
+
+        let roots = parse_roots_file(&roots_file);
+        assert_eq!(roots, [roots_dir.join("ssd"), roots_dir.join("share")]);
+
+        let installations = scan_installation_roots(
+            OperatingSystem::Linux,
+            &roots,
+            false,
+            None,
+            &Reporter::default(),
+        )
+        .unwrap();
+        let versions: Vec<_> = installations.iter().map(|x| x.version.as_str()).collect();
+        assert_eq!(versions, ["2022.3.11f1", "2021.3.5f1"]);
+    }
+
+    #[test]
+    fn test_parse_secondary_install_path() {
+        assert_eq!(
+            parse_secondary_install_path("\"D:\\\\Unity\\\\Editors\"\n"),
+            Some(PathBuf::from("D:\\Unity\\Editors"))
+        );
+        assert_eq!(
+            parse_secondary_install_path("\"\\/mnt\\/editors \\u00e9\""),
+            Some(PathBuf::from("/mnt/editors \u{e9}"))
+        );
+        for malformed in [
+            "",
+            "\"\"",
+            "\"  \"",
+            "null",
+            "\"unterminated",
+            "\"bad\\q\"",
+            "\"a\"b\"",
+        ] {
+            assert_eq!(
+                parse_secondary_install_path(malformed),
+                None,
+                "{malformed:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_discovery_merges_secondary_install_path() {
+        let hub_dir = fixture_dir("secondary-install-path");
+        for (root, version) in [("default", "2021.3.5f1"), ("secondary", "2022.3.11f1")] {
+            editor_fixture(&hub_dir.join(root), version, OperatingSystem::Linux, false);
+        }
+        let config_path = hub_dir.join("config");
+        std::fs::create_dir_all(&config_path).unwrap();
+        let secondary = hub_dir.join("secondary").display().to_string();
+        std::fs::write(
+            config_path.join("secondaryInstallPath.json"),
+            format!("\"{}\"", secondary.replace('\\', "\\\\")),
+        )
+        .unwrap();
+
+        let secondary_install_path = get_secondary_install_path(&config_path);
+        assert_eq!(secondary_install_path, Some(hub_dir.join("secondary")));
+
+        let roots = unityhub_roots(hub_dir.join("default"), secondary_install_path, &[]);
+        let installations =
+            scan_unityhub_roots(OperatingSystem::Linux, &roots, false, &Reporter::default())
+                .unwrap();
+        let versions: Vec<_> = installations.iter().map(|x| x.version.as_str()).collect();
+        assert_eq!(versions, ["2021.3.5f1", "2022.3.11f1"]);
+    }
+
+    #[test]
+    fn test_editor_root_override_ignores_secondary_install_path() {
+        let hub_dir = fixture_dir("editor-root-secondary");
+        let config_path = hub_dir.join("config");
+        std::fs::create_dir_all(&config_path).unwrap();
+        std::fs::create_dir_all(hub_dir.join("secondary")).unwrap();
+        let secondary = hub_dir.join("secondary").display().to_string();
+        std::fs::write(
+            config_path.join("secondaryInstallPath.json"),
+            format!("\"{}\"", secondary.replace('\\', "\\\\")),
+        )
+        .unwrap();
+        let editor_root = hub_dir.join("editors");
+        std::fs::create_dir_all(&editor_root).unwrap();
+        let extra_root = hub_dir.join("extra");
+
+        let roots = get_roots_with_override(
+            OperatingSystem::Linux,
+            Some(editor_root.clone().into_os_string()),
+            Some(config_path.clone()),
+            std::slice::from_ref(&extra_root),
+            &Reporter::default(),
+        );
+        assert_eq!(roots.unwrap(), [editor_root, extra_root.clone()]);
+
+        let roots = get_roots_with_override(
+            OperatingSystem::Linux,
+            Some(OsString::new()),
+            Some(config_path),
+            std::slice::from_ref(&extra_root),
+            &Reporter::default(),
+        )
+        .unwrap();
+        assert_eq!(roots[1..], [hub_dir.join("secondary"), extra_root]);
+    }
+
+    #[test]
+    fn test_missing_secondary_install_config_is_ignored() {
+        let config_path = fixture_dir("secondary-install-path-missing");
+        assert_eq!(get_secondary_install_path(&config_path), None);
+
+        std::fs::write(config_path.join("secondaryInstallPath.json"), "{not json").unwrap();
+        assert_eq!(get_secondary_install_path(&config_path), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_scan_skips_non_unicode_editor_directories() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let installations_path = fixture_dir("non-unicode-editor");
+        for name in [
+            std::ffi::OsStr::new("2022.3.11f1"),
+            std::ffi::OsStr::from_bytes(b"2021.3.\xff5f1"),
+        ] {
+            editor_fixture(&installations_path, name, OperatingSystem::Linux, false);
+        }
+
+        let installations = scan_installations(
+            OperatingSystem::Linux,
+            &installations_path,
+            None,
+            &Reporter::default(),
+        );
+        assert_eq!(
+            installations.unwrap(),
+            [UnityInstallation {
+                version: "2022.3.11f1".to_owned(),
+                path: installations_path.join("2022.3.11f1"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_roots_file_skips_nonexistent_roots() {
+        let roots = [fixture_dir("roots-file-missing").join("nope")];
+        assert!(scan_installation_roots(
+            OperatingSystem::Linux,
+            &roots,
+            false,
+            None,
+            &Reporter::default()
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    /// A progress sink whose output can still be read once it's been handed out
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_slow_scan_reports_progress() {
+        let installations_path = fixture_dir("scan-progress");
+        for version in ["2022.3.11f1", "2021.3.5f1"] {
+            editor_fixture(&installations_path, version, OperatingSystem::Linux, false);
+        }
+        let scan = |threshold| {
+            let buffer = SharedBuffer::default();
+            let progress = std::sync::Mutex::new(ScanProgress::new(
+                Box::new(buffer.clone()),
+                threshold,
+                std::time::Duration::ZERO,
+            ));
+            let installations = scan_installations(
+                OperatingSystem::Linux,
+                &installations_path,
+                Some(&progress),
+                &Reporter::default(),
+            )
+            .unwrap();
+            progress.into_inner().unwrap().finish();
+            assert_eq!(installations.len(), 2);
+            let output = buffer.0.lock().unwrap().clone();
+            String::from_utf8(output).unwrap()
+        };
+
+        let output = scan(std::time::Duration::ZERO);
+        assert!(
+            output.ends_with("\rscanning install roots: 2 directories so far\n"),
+            "{output:?}"
+        );
+        assert_eq!(scan(std::time::Duration::from_secs(3600)), "");
+    }
+
+    #[test]
+    fn test_parallel_scan_shares_progress() {
+        let roots_dir = fixture_dir("scan-progress-parallel");
+        let roots = ["a", "b", "c"].map(|root| roots_dir.join(root));
+        for (root, version) in roots
+            .iter()
+            .zip(["2022.3.11f1", "2021.3.5f1", "2023.1.0f1"])
+        {
+            editor_fixture(root, version, OperatingSystem::Linux, false);
+            editor_fixture(root, "2020.3.48f1", OperatingSystem::Linux, false);
+        }
+
+        let buffer = SharedBuffer::default();
+        let progress = std::sync::Mutex::new(ScanProgress::new(
+            Box::new(buffer.clone()),
+            std::time::Duration::ZERO,
+            std::time::Duration::ZERO,
+        ));
+        scan_installation_roots(
+            OperatingSystem::Linux,
+            &roots,
+            true,
+            Some(&progress),
+            &Reporter::default(),
+        )
+        .unwrap();
+        progress.into_inner().unwrap().finish();
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output.matches('\n').count(), 1, "{output:?}");
+        assert!(
+            output.ends_with("\rscanning install roots: 6 directories so far\n"),
+            "{output:?}"
+        );
+    }
+
+    #[test]
+    fn test_scan_failure_on_first_root_is_an_error() {
+        let roots_dir = fixture_dir("scan-failure-roots");
+        editor_fixture(
+            &roots_dir.join("share"),
+            "2022.3.11f1",
+            OperatingSystem::Linux,
+            false,
+        );
+        // Reading a file as a directory fails with a real IO error, even when running as root
+        std::fs::write(roots_dir.join("unreadable"), "").unwrap();
+        let [unreadable, missing, share] =
+            ["unreadable", "missing", "share"].map(|root| roots_dir.join(root));
+
+        for parallel_scan in [false, true] {
+            let scan = |roots: &[PathBuf]| {
+                scan_installation_roots(
+                    OperatingSystem::Linux,
+                    roots,
+                    parallel_scan,
+                    None,
+                    &Reporter::default(),
+                )
+            };
+            assert!(matches!(
+                scan(&[unreadable.clone(), share.clone()]),
+                Err(AppError::InstallationScanFailed(..))
+            ));
+            assert!(matches!(
+                scan(&[missing.clone(), unreadable.clone()]),
+                Err(AppError::InstallationScanFailed(..))
+            ));
+            assert_eq!(scan(&[share.clone(), unreadable.clone()]).unwrap().len(), 1);
+            assert_eq!(scan(&[missing.clone(), share.clone()]).unwrap().len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_scan_skips_unreadable_editor_directories() {
+        let installations_path = fixture_dir("unreadable-editor");
+        // Probing a binary below a file fails with a real IO error, even when running as root
+        std::fs::create_dir_all(installations_path.join("2021.3.5f1")).unwrap();
+        std::fs::write(installations_path.join("2021.3.5f1/Editor"), "").unwrap();
+
+        assert!(matches!(
+            scan_installations(
+                OperatingSystem::Linux,
+                &installations_path,
+                None,
+                &Reporter::default()
+            ),
+            Err(AppError::InstallationScanFailed(..))
+        ));
+
+        editor_fixture(
+            &installations_path,
+            "2022.3.11f1",
+            OperatingSystem::Linux,
+            false,
+        );
+        let installations = scan_installations(
+            OperatingSystem::Linux,
+            &installations_path,
+            None,
+            &Reporter::default(),
+        );
+        let versions: Vec<_> = installations
+            .unwrap()
+            .into_iter()
+            .map(|installation| installation.version)
+            .collect();
+        assert_eq!(versions, ["2022.3.11f1"]);
+    }
+
+    #[test]
+    fn test_parse_platform() {
+        assert_eq!(parse_os("macos"), Ok(OperatingSystem::MacOS));
+        assert_eq!(parse_os("freebsd"), Err(AppError::UnsupportedOS));
+        assert_eq!(parse_arch("aarch64"), Ok(Architecture::Arm64));
+        assert_eq!(parse_arch("x86_64"), Ok(Architecture::X86_64));
+        assert_eq!(
+            parse_arch("riscv64"),
+            Err(AppError::UnsupportedArchitecture)
+        );
+    }
+
+    #[test]
+    fn test_get_host_platform() {
+        let platform = get_host_platform().unwrap();
+        assert_eq!(platform.os, parse_os(std::env::consts::OS).unwrap());
+        assert_eq!(platform.arch, parse_arch(std::env::consts::ARCH).unwrap());
+    }
+
+    #[test]
+    fn test_project_version_override_wins() {
+        let workdir = project_fixture("project-version-override", "2022.3.11f1");
+        std::fs::write(
+            workdir.join("ProjectSettings/ProjectVersionOverride.txt"),
+            "m_EditorVersion: 2022.3.20f1\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_project_version(&workdir, &Reporter::default())
+                .unwrap()
+                .version,
+            "2022.3.20f1"
+        );
+    }
+
+    #[test]
+    fn test_project_version_without_override() {
+        let workdir = project_fixture("project-version-no-override", "2022.3.11f1");
+        assert_eq!(
+            locate_project_version_file(&workdir).unwrap(),
+            workdir.join("ProjectSettings/ProjectVersion.txt")
+        );
+        assert_eq!(
+            read_project_version(&workdir, &Reporter::default())
+                .unwrap()
+                .version,
+            "2022.3.11f1"
+        );
+    }
+
+    #[test]
+    fn test_parallel_scan_matches_sequential() {
+        let roots_dir = fixture_dir("parallel-scan");
+        let mut roots = Vec::new();
+        for (root, versions) in [
+            ("a", &["2022.3.11f1", "2021.3.5f1"][..]),
+            ("b", &["2023.1.0f1"][..]),
+            ("missing", &[][..]),
+            ("c", &["2022.3.11f1", "2020.3.48f1"][..]),
+        ] {
+            roots.push(roots_dir.join(root));
+            for version in versions {
+                editor_fixture(
+                    &roots_dir.join(root),
+                    version,
+                    OperatingSystem::Linux,
+                    false,
+                );
+            }
+        }
+
+        let paths = |installations: UnityInstallationCollection| {
+            installations
+                .into_iter()
+                .map(|x| x.path)
+                .collect::<Vec<_>>()
+        };
+        let sequential = scan_installation_roots(
+            OperatingSystem::Linux,
+            &roots,
+            false,
+            None,
+            &Reporter::default(),
+        )
+        .unwrap();
+        let parallel = scan_installation_roots(
+            OperatingSystem::Linux,
+            &roots,
+            true,
+            None,
+            &Reporter::default(),
+        )
+        .unwrap();
+        assert_eq!(sequential.len(), 5);
+        assert_eq!(paths(sequential), paths(parallel));
+    }
+
+    #[test]
+    fn test_resolve_populates_merge_context() {
+        let workdir = project_fixture("resolve-context", "2022.3.11f1");
+        std::fs::write(
+            workdir.join("ProjectSettings/ProjectVersion.txt"),
+            "m_EditorVersion: 2022.3.11f1\nm_EditorVersionWithRevision: 2022.3.11f1 (d00248457e15)\n",
+        )
+        .unwrap();
+        let installations_path = fixture_dir("resolve-context-editors");
+        for version in ["2022.3.11f1", "2023.1.0f1"] {
+            editor_fixture(&installations_path, version, OperatingSystem::Windows, true);
+        }
+        let installations = vec![
+            UnityInstallation {
+                version: "2023.1.0f1".to_owned(),
+                path: installations_path.join("2023.1.0f1"),
+            },
+            UnityInstallation {
+                version: "2022.3.11f1".to_owned(),
+                path: installations_path.join("2022.3.11f1"),
+            },
+        ];
+
+        let context = resolve_with(
+            OperatingSystem::Windows,
+            &workdir,
+            &scanned(installations.clone()),
+            false,
+            &Reporter::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            context,
+            MergeContext {
+                project_root: Some(workdir),
+                project_version: Some(ProjectVersion {
+                    version: "2022.3.11f1".to_owned(),
+                    revision: Some("d00248457e15".to_owned()),
+                }),
+                installation: installations[1].clone(),
+                tool_path: installations_path
+                    .join("2022.3.11f1/Editor/Data/Tools/UnityYAMLMerge.exe"),
+                source: SelectionSource::ProjectVersion,
+            }
+        );
+    }
+
+    #[test]
+    fn test_version_channel_casing_is_normalized() {
+        let installations_path = fixture_dir("channel-casing");
+        editor_fixture(
+            &installations_path,
+            "2022.3.11F1",
+            OperatingSystem::Linux,
+            false,
+        );
+
+        let installations = scan_installations(
+            OperatingSystem::Linux,
+            &installations_path,
+            None,
+            &Reporter::default(),
+        )
+        .unwrap();
+        assert_eq!(installations[0].version, "2022.3.11f1");
+        assert_eq!(
+            installations[0].path,
+            installations_path.join("2022.3.11F1")
+        );
+
+        let workdir = project_fixture("channel-casing-project", "2022.3.11f1");
+        let Selection {
+            installation,
+            source,
+            ..
+        } = choose_best_installation(&workdir, &installations, false, &Reporter::default())
+            .unwrap();
+        assert_eq!(installation.path, installations_path.join("2022.3.11F1"));
+        assert_eq!(source, SelectionSource::ProjectVersion);
+
+        let project_version =
+            parse_project_version_file("m_EditorVersion: 2022.3.11F1\n", &Reporter::default());
+        assert_eq!(project_version.unwrap().version, "2022.3.11f1");
+    }
+
+    #[test]
+    fn test_scan_and_get_yamltool_per_os() {
+        for (os, unity_exe, yamltool) in [
+            (
+                OperatingSystem::Windows,
+                "Editor/Unity.exe",
+                "Editor/Data/Tools/UnityYAMLMerge.exe",
+            ),
+            (
+                OperatingSystem::MacOS,
+                "Unity.app/Contents/MacOS/Unity",
+                "Unity.app/Contents/Tools/UnityYAMLMerge",
+            ),
+            (
+                OperatingSystem::Linux,
+                "Editor/Unity",
+                "Editor/Data/Tools/UnityYAMLMerge",
+            ),
+        ] {
+            // Lay the editor out by hand, the way Unity Hub installs it on each OS
+            let installations_path = fixture_dir(&format!("yamltool-{os:?}"));
+            let editor_path = installations_path.join("2022.3.11f1");
+            for file in [unity_exe, yamltool] {
+                let file = editor_path.join(file);
+                std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+                std::fs::write(file, "").unwrap();
+            }
+
+            let installations =
+                scan_installations(os, &installations_path, None, &Reporter::default()).unwrap();
+            assert_eq!(
+                installations,
+                [UnityInstallation {
+                    version: "2022.3.11f1".to_owned(),
+                    path: editor_path.clone(),
+                }]
+            );
+            assert_eq!(
+                get_yamltool(os, &installations[0].path),
+                Ok(editor_path.join(yamltool))
+            );
+
+            std::fs::remove_file(editor_path.join(yamltool)).unwrap();
+            assert_eq!(
+                get_yamltool(os, &installations[0].path),
+                Err(AppError::YamlToolNotFound)
+            );
+        }
+    }
+
+    #[test]
+    fn test_choose_best_installation_latest_is_semver_aware() {
+        let workdir = fixture_dir("latest-semver");
+        let installations = installations_of(&["2022.3.11f1", "2022.3.9f1", "not-a-version"]);
+        let Selection { installation, .. } =
+            choose_best_installation(&workdir, &installations, false, &Reporter::default())
+                .unwrap();
+        assert_eq!(installation.version, "2022.3.11f1");
+
+        let installations = installations_of(&["2023.1.0f1", "2023.1.0b20", "2023.1.0a5"]);
+        let Selection { installation, .. } =
+            choose_best_installation(&workdir, &installations, false, &Reporter::default())
+                .unwrap();
+        assert_eq!(installation.version, "2023.1.0f1");
+    }
+
+    #[test]
+    fn test_choose_best_installation_version_not_installed() {
+        let workdir = project_fixture("version-not-installed", "2022.3.11f1");
+        let installations =
+            installations_of(&["2021.3.11f1", "2022.3.5f1", "2022.3.14f1", "2023.1.0f1"]);
+        let err = choose_best_installation(&workdir, &installations, false, &Reporter::default())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            AppError::RequiredVersionNotInstalled(
+                "2022.3.11f1".to_owned(),
+                Some("2022.3.14f1".to_owned())
+            )
+        );
+        assert_eq!(
+            err.to_string(),
+            "Unity 2022.3.11f1 is required by this project but is not installed (closest installed: 2022.3.14f1)"
+        );
+
+        let installations = installations_of(&["2021.3.11f1"]);
+        let err = choose_best_installation(&workdir, &installations, false, &Reporter::default())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            AppError::RequiredVersionNotInstalled("2022.3.11f1".to_owned(), None)
+        );
+    }
+
+    #[test]
+    fn test_choose_best_installation_patch_fallback() {
+        let workdir = project_fixture("patch-fallback", "2022.3.11f1");
+        let installations = installations_of(&[
+            "2022.3.12f1",
+            "2022.3.9f1",
+            "2022.3.20b1",
+            "2022.2.30f1",
+            "2023.1.0f1",
+        ]);
+        let Selection {
+            installation,
+            source,
+            ..
+        } = choose_best_installation(&workdir, &installations, true, &Reporter::default()).unwrap();
+        assert_eq!(installation.version, "2022.3.12f1");
+        assert_eq!(source, SelectionSource::PatchFallback);
+
+        let installations = installations_of(&["2022.3.11f1", "2022.3.12f1"]);
+        let Selection {
+            installation,
+            source,
+            ..
+        } = choose_best_installation(&workdir, &installations, true, &Reporter::default()).unwrap();
+        assert_eq!(installation.version, "2022.3.11f1");
+        assert_eq!(source, SelectionSource::ProjectVersion);
+
+        let installations = installations_of(&["2022.2.30f1", "2022.3.20b1", "2023.1.0f1"]);
+        let err = choose_best_installation(&workdir, &installations, true, &Reporter::default())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            AppError::RequiredVersionNotInstalled(
+                "2022.3.11f1".to_owned(),
+                Some("2022.3.20b1".to_owned())
+            )
+        );
+    }
+
+    #[test]
+    fn test_located_installation() {
+        let editor_path = editor_fixture(
+            &fixture_dir("located"),
+            "2022.3.11f1",
+            OperatingSystem::Windows,
+            false,
+        );
+
+        let installation = get_located_installation(OperatingSystem::Windows, &editor_path);
+        assert_eq!(
+            installation.unwrap(),
+            UnityInstallation {
+                version: "2022.3.11f1".to_owned(),
+                path: editor_path.clone(),
+            }
+        );
+
+        let err = get_located_installation(OperatingSystem::Linux, &editor_path).err();
+        assert!(matches!(err, Some(AppError::InvalidEditorPath(..))));
+    }
+
+    #[test]
+    fn test_forced_installation_ignores_project_version() {
+        let editors = fixture_dir("forced-editors");
+        let editor_path = editor_fixture(&editors, "2022.3.11f1", OperatingSystem::Linux, true);
+        let workdir = project_fixture("forced-project", "2021.3.1f1");
+
+        let installation = get_located_installation(OperatingSystem::Linux, &editor_path).unwrap();
+        let discovery = Discovery::Forced(installation.clone());
+        let context = resolve_with(
+            OperatingSystem::Linux,
+            &workdir,
+            &discovery,
+            false,
+            &Reporter::default(),
+        )
+        .unwrap();
+        assert_eq!(context.installation, installation);
+        assert_eq!(context.source, SelectionSource::Forced);
+        assert_eq!(
+            context
+                .project_version
+                .map(|project_version| project_version.version),
+            Some("2021.3.1f1".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_located_installation_needs_version_name() {
+        let editor_path = editor_fixture(
+            &fixture_dir("located-unversioned"),
+            "unity-lts",
+            OperatingSystem::Linux,
+            false,
+        );
+
+        let err = get_located_installation(OperatingSystem::Linux, &editor_path).err();
+        assert_eq!(
+            err.unwrap().to_string(),
+            format!(
+                "UYAMLT_EDITOR_PATH ({}) is not a usable editor installation: its directory name is not a Unity version",
+                editor_path.display()
+            )
+        );
     }
 }
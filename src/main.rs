@@ -1,17 +1,22 @@
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::restriction)]
 #![allow(
+    clippy::blanket_clippy_restriction_lints,
     clippy::implicit_return,
     clippy::shadow_reuse,
     clippy::single_call_fn,
-    clippy::question_mark_used
+    clippy::question_mark_used,
+    clippy::print_stderr,
+    reason = "restriction lints are opted into as a group, these are the ones we don't follow"
 )]
 
+use std::{env, process};
+
 fn main() {
-    match uyamlt::run(&std::env::args().collect::<Vec<_>>()) {
-        Ok(code) => std::process::exit(code),
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(0)
+    match uyamlt::run(&env::args().collect::<Vec<_>>()) {
+        Ok(code) => process::exit(code),
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(1)
         }
     }
 }
@@ -0,0 +1,148 @@
+use crate::AppError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Release channel of a Unity version, ordered from least to most stable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReleaseChannel {
+    Alpha,
+    Beta,
+    Final,
+    Patch,
+}
+
+impl ReleaseChannel {
+    fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'a' => Some(Self::Alpha),
+            'b' => Some(Self::Beta),
+            'f' => Some(Self::Final),
+            'p' => Some(Self::Patch),
+            _ => None,
+        }
+    }
+
+    const fn letter(self) -> char {
+        match self {
+            Self::Alpha => 'a',
+            Self::Beta => 'b',
+            Self::Final => 'f',
+            Self::Patch => 'p',
+        }
+    }
+}
+
+/// A parsed Unity version, e.g. `2022.3.11f1`.
+/// Compares numerically component by component, so `2022.3.9f1 < 2022.3.11f1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnityVersion {
+    pub year: u32,
+    pub stream: u32,
+    pub patch: u32,
+    pub channel: ReleaseChannel,
+    pub build: u32,
+}
+
+impl FromStr for UnityVersion {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AppError::InvalidVersion(s.to_owned());
+        let mut parts = s.splitn(3, '.');
+        let (Some(year), Some(stream), Some(rest)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let channel_at = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let (patch, rest) = rest.split_at(channel_at);
+        let mut rest = rest.chars();
+        let channel = rest
+            .next()
+            .and_then(|letter| ReleaseChannel::from_letter(letter.to_ascii_lowercase()))
+            .ok_or_else(invalid)?;
+
+        let number = |s: &str| s.parse::<u32>().map_err(|_| invalid());
+        Ok(Self {
+            year: number(year)?,
+            stream: number(stream)?,
+            patch: number(patch)?,
+            channel,
+            build: number(rest.as_str())?,
+        })
+    }
+}
+
+impl fmt::Display for UnityVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}{}{}",
+            self.year,
+            self.stream,
+            self.patch,
+            self.channel.letter(),
+            self.build
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn version(s: &str) -> UnityVersion {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            version("2022.3.11f1"),
+            UnityVersion {
+                year: 2022,
+                stream: 3,
+                patch: 11,
+                channel: ReleaseChannel::Final,
+                build: 1,
+            }
+        );
+        assert_eq!(version("6000.0.23b12").to_string(), "6000.0.23b12");
+    }
+
+    #[test]
+    fn test_parse_version_invalid() {
+        for invalid in [
+            "",
+            "2022",
+            "2022.3",
+            "2022.3.11",
+            "2022.3.11x1",
+            "2022.3.f1",
+            "2022.3.11f",
+        ] {
+            assert_eq!(
+                invalid.parse::<UnityVersion>(),
+                Err(AppError::InvalidVersion(invalid.to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_version_ordering_is_numeric() {
+        assert!(version("2022.3.9f1") < version("2022.3.11f1"));
+        assert!(version("2022.9.0f1") < version("2022.10.0f1"));
+        assert!(version("2021.3.30f1") < version("2022.1.0f1"));
+    }
+
+    #[test]
+    fn test_version_ordering_by_channel() {
+        assert!(version("2023.1.0a5") < version("2023.1.0b1"));
+        assert!(version("2023.1.0b20") < version("2023.1.0f1"));
+        assert!(version("2023.1.0f1") < version("2023.1.0p1"));
+        assert!(version("2023.1.0p3") < version("2023.1.1a1"));
+        assert!(version("2023.1.0f1") < version("2023.1.0f2"));
+    }
+}
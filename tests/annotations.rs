@@ -0,0 +1,28 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{editor_fixture, fixture_dir, project_fixture, uyamlt};
+
+#[test]
+fn test_annotations_are_written_to_stderr() {
+    let editors = fixture_dir("annotations-editors");
+    let editor_path = editor_fixture(&editors, "2022.3.11f1");
+    let workdir = project_fixture("annotations-project", "2021.3.1f1");
+
+    let output = uyamlt(&workdir)
+        .args(["--annotations", "github", "--dry-run", "merge"])
+        .env("UYAMLT_EDITOR_PATH", &editor_path)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("installation: 2022.3.11f1 "), "{stdout}");
+    assert!(!stdout.contains("::warning"), "{stdout}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Warning: this project pins Unity 2021.3.1f1, but UYAMLT_EDITOR_PATH forces 2022.3.11f1\n\
+         ::warning title=uyamlt::this project pins Unity 2021.3.1f1, but UYAMLT_EDITOR_PATH forces 2022.3.11f1\n"
+    );
+}
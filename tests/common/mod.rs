@@ -0,0 +1,50 @@
+//! Fixtures shared by the integration tests, which drive the real `uyamlt` binary
+#![allow(
+    dead_code,
+    reason = "every test binary uses a different subset of these"
+)]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Creates a fresh, empty directory for a test fixture
+pub fn fixture_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("uyamlt-it-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::canonicalize(dir).unwrap()
+}
+
+/// Creates a `version`-named editor install under `root` with a `UnityYAMLMerge`,
+/// laid out the way Linux's Unity Hub does it
+pub fn editor_fixture(root: &Path, version: &str) -> PathBuf {
+    let editor_path = root.join(version);
+    std::fs::create_dir_all(editor_path.join("Editor/Data/Tools")).unwrap();
+    std::fs::write(editor_path.join("Editor/Unity"), "").unwrap();
+    std::fs::write(editor_path.join("Editor/Data/Tools/UnityYAMLMerge"), "").unwrap();
+    editor_path
+}
+
+/// Creates a project fixture pinned to the given editor version
+pub fn project_fixture(name: &str, version: &str) -> PathBuf {
+    let dir = fixture_dir(name);
+    std::fs::create_dir_all(dir.join("ProjectSettings")).unwrap();
+    std::fs::write(
+        dir.join("ProjectSettings/ProjectVersion.txt"),
+        format!("m_EditorVersion: {version}\n"),
+    )
+    .unwrap();
+    dir
+}
+
+/// A `uyamlt` invocation from `workdir`, isolated from the caller's uyamlt and Unity Hub configuration
+pub fn uyamlt(workdir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_uyamlt"));
+    command.current_dir(workdir).env("HOME", workdir);
+    for (name, _) in std::env::vars_os() {
+        if name.to_string_lossy().starts_with("UYAMLT_") {
+            command.env_remove(name);
+        }
+    }
+    command
+}
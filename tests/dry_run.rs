@@ -0,0 +1,36 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{editor_fixture, fixture_dir, uyamlt};
+
+#[test]
+fn test_dry_run_prints_resolved_command() {
+    let editors = fixture_dir("dry-run");
+    let editor_path = editor_fixture(&editors, "2022.3.11f1");
+    let tool_path = editor_path.join("Editor/Data/Tools/UnityYAMLMerge");
+
+    for (flag, env) in [(Some("--dry-run"), None), (None, Some("1"))] {
+        let mut command = uyamlt(&editors);
+        command
+            .args(flag)
+            .arg("merge")
+            .args(["-p", "base", "remote", "local", "merged"])
+            .env("UYAMLT_EDITOR_PATH", &editor_path);
+        if let Some(env) = env {
+            command.env("UYAMLT_DRY_RUN", env);
+        }
+        let output = command.output().unwrap();
+
+        assert_eq!(output.status.code(), Some(0));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            format!(
+                "installation: 2022.3.11f1 ({})\ntool: {}\ncommand: {:?} \"merge\" \"-p\" \"base\" \"remote\" \"local\" \"merged\"\n",
+                editor_path.display(),
+                tool_path.display(),
+                tool_path,
+            )
+        );
+    }
+}
@@ -0,0 +1,30 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{editor_fixture, fixture_dir, project_fixture, uyamlt};
+
+#[test]
+fn test_editor_path_overrides_project_version() {
+    let editors = fixture_dir("editor-path-editors");
+    let editor_path = editor_fixture(&editors, "2022.3.11f1");
+    let workdir = project_fixture("editor-path-project", "2021.3.1f1");
+
+    let output = uyamlt(&workdir)
+        .args(["--dry-run", "merge"])
+        .env("UYAMLT_EDITOR_PATH", &editor_path)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(
+        String::from_utf8_lossy(&output.stdout).starts_with(&format!(
+            "installation: 2022.3.11f1 ({})\n",
+            editor_path.display()
+        ))
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Warning: this project pins Unity 2021.3.1f1, but UYAMLT_EDITOR_PATH forces 2022.3.11f1\n"
+    );
+}
@@ -0,0 +1,16 @@
+mod common;
+
+use common::{fixture_dir, uyamlt};
+
+#[test]
+fn test_failure_exits_non_zero() {
+    let output = uyamlt(&fixture_dir("exit-code"))
+        .args(["merge", "base", "remote", "local", "merged"])
+        .env("UYAMLT_EDITOR_PATH", "/nonexistent/uyamlt/2022.3.11f1")
+        .env("UYAMLT_DRY_RUN", "1")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "));
+}
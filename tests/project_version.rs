@@ -0,0 +1,29 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{editor_fixture, fixture_dir, project_fixture, uyamlt};
+
+#[test]
+fn test_project_version_warnings_are_printed_once() {
+    let editors = fixture_dir("warned-once-editors");
+    editor_fixture(&editors, "2022.3.11f1");
+    let workdir = project_fixture("warned-once-project", "2022.3.11f1");
+    std::fs::write(
+        workdir.join("ProjectSettings/ProjectVersion.txt"),
+        "m_EditorVersion: 2022.3.10f1\nm_EditorVersionWithRevision: 2022.3.11f1 (d00248457e15)\n",
+    )
+    .unwrap();
+
+    let output = uyamlt(&workdir)
+        .args(["--dry-run", "merge"])
+        .env("UYAMLT_EDITOR_ROOT", &editors)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Warning: ProjectVersion.txt disagrees with itself (2022.3.10f1 vs 2022.3.11f1), using 2022.3.11f1\n"
+    );
+}
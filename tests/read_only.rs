@@ -0,0 +1,70 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use common::{editor_fixture, fixture_dir, project_fixture, uyamlt};
+
+/// Lists every entry under `root` with its size and modification time
+fn snapshot_tree(root: &Path) -> Vec<(PathBuf, u64, std::time::SystemTime)> {
+    let mut entries = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir).unwrap().map(Result::unwrap) {
+            let metadata = entry.metadata().unwrap();
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            }
+            entries.push((entry.path(), metadata.len(), metadata.modified().unwrap()));
+        }
+    }
+    entries.sort();
+    entries
+}
+
+/// Sets `mode` on every directory under `root`, ignoring directories it can't change
+fn set_tree_mode(root: &Path, mode: u32) {
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir).unwrap().map(Result::unwrap) {
+            if entry.path().is_dir() {
+                pending.push(entry.path());
+            }
+        }
+        let _ = std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(mode));
+    }
+}
+
+#[test]
+fn test_detection_on_read_only_install_tree() {
+    let editors = fixture_dir("read-only-editors");
+    let editor_path = editor_fixture(&editors, "2022.3.11f1");
+    let tool_path = editor_path.join("Editor/Data/Tools/UnityYAMLMerge");
+    let workdir = project_fixture("read-only-project", "2022.3.11f1");
+
+    // Best effort: root can still write, but any write would show up in the snapshot
+    set_tree_mode(&editors, 0o555);
+    let before = snapshot_tree(&editors);
+
+    let output = uyamlt(&workdir)
+        .args(["--dry-run", "merge"])
+        .env("UYAMLT_EDITOR_ROOT", &editors)
+        .output()
+        .unwrap();
+    let after = snapshot_tree(&editors);
+    set_tree_mode(&editors, 0o755);
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with(&format!(
+            "installation: 2022.3.11f1 ({})\ntool: {}\n",
+            editor_path.display(),
+            tool_path.display()
+        )),
+        "{stdout}"
+    );
+    assert_eq!(before, after);
+}
@@ -0,0 +1,43 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{editor_fixture, fixture_dir, project_fixture, uyamlt};
+
+#[test]
+fn test_verify_fails_for_mismatched_project() {
+    let editors = fixture_dir("verify-mismatched-editors");
+    editor_fixture(&editors, "2021.3.5f1");
+    let workdir = project_fixture("verify-mismatched-project", "2022.3.11f1");
+
+    let output = uyamlt(&workdir)
+        .arg("verify")
+        .env("UYAMLT_EDITOR_ROOT", &editors)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("verify: FAILED"), "{stderr}");
+}
+
+#[test]
+fn test_verify_succeeds_for_matching_project() {
+    let editors = fixture_dir("verify-matching-editors");
+    editor_fixture(&editors, "2022.3.11f1");
+    let workdir = project_fixture("verify-matching-project", "2022.3.11f1");
+
+    let output = uyamlt(&workdir)
+        .arg("verify")
+        .env("UYAMLT_EDITOR_ROOT", &editors)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("verify: OK (Unity 2022.3.11f1 with "),
+        "{stdout}"
+    );
+}